@@ -1,3 +1,5 @@
+doc_item::styles!(".custom { background: #c4ffd7; border-color: #7bdba1; }");
+
 #[doc_item::docbox(content = "A custom docbox", class = "custom")]
 #[doc_item::short_docbox(content = "Custom", class = "custom")]
 pub fn foo() {}
@@ -3,8 +3,9 @@
 /// The docbox will indicate the function is experimental. It will also appear semi-transparent on
 /// module lists.
 #[doc_item::docbox(
-    content = "<span class='emoji'>🔬</span> This is an experimental API.",
-    class = "unstable"
+    content = "This is an experimental API.",
+    class = "unstable",
+    icon = "🔬"
 )]
 #[doc_item::short_docbox(content = "Experimental", class = "unstable")]
 #[doc_item::semi_transparent]
@@ -0,0 +1,406 @@
+//! Statically places the markup generated by `doc_item` within generated documentation.
+//!
+//! The attributes provided by `doc_item` emit markup that is relocated by a bootstrap script when
+//! the documentation is viewed. This tool performs the same relocations ahead of time, directly on
+//! the HTML files generated by rustdoc, and removes the bootstrap afterwards. The resulting
+//! documentation is displayed the same way, but does not require JavaScript.
+//!
+//! Run it after generating the documentation:
+//!
+//! ```bash
+//! $ cargo doc --no-deps
+//! $ doc_item-postprocess target/doc
+//! ```
+//!
+//! If no directory is provided, `target/doc` is used.
+
+#![warn(
+    clippy::cargo,
+    clippy::nursery,
+    clippy::pedantic,
+    unused_qualifications
+)]
+
+#[path = "../layouts.rs"]
+mod layouts;
+
+use ego_tree::{NodeId, NodeRef};
+use html5ever::{ns, LocalName, QualName};
+use layouts::{BOOTSTRAP, HEADINGS, MARKER, RIGHTSIDES, ROWS, STYLES};
+use scraper::{node::Text, ElementRef, Html, Node, Selector, StrTendril};
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+fn compile(selector: &str) -> Selector {
+    Selector::parse(selector).unwrap()
+}
+
+/// The selectors used while processing pages, compiled once up front.
+struct Selectors {
+    marked: Selector,
+    bootstrap: Selector,
+    styles: Selector,
+    head: Selector,
+    docblock: Selector,
+    top_doc: Selector,
+    paragraph: Selector,
+    link: Selector,
+    since: Selector,
+    rows: Vec<(Selector, Selector)>,
+    headings: Vec<Selector>,
+    rightsides: Vec<Selector>,
+}
+
+impl Selectors {
+    fn new() -> Self {
+        Self {
+            marked: compile(&format!("[{MARKER}]")),
+            bootstrap: compile(&format!("[{BOOTSTRAP}]")),
+            styles: compile(&format!("[{STYLES}]")),
+            head: compile("head"),
+            docblock: compile(".docblock"),
+            top_doc: compile(".top-doc"),
+            paragraph: compile("p"),
+            link: compile("a"),
+            since: compile(".since"),
+            rows: ROWS
+                .iter()
+                .map(|(summary, name)| (compile(summary), compile(name)))
+                .collect(),
+            headings: HEADINGS.iter().map(|heading| compile(heading)).collect(),
+            rightsides: RIGHTSIDES
+                .iter()
+                .map(|rightside| compile(rightside))
+                .collect(),
+        }
+    }
+}
+
+fn matches(node: NodeRef<Node>, selector: &Selector) -> bool {
+    ElementRef::wrap(node).map_or(false, |element| selector.matches(&element))
+}
+
+fn is_element(node: &NodeRef<Node>) -> bool {
+    node.value().is_element()
+}
+
+/// Returns the ids of the descendants of the node matching the selector.
+fn select(html: &Html, id: NodeId, selector: &Selector) -> Vec<NodeId> {
+    html.tree
+        .get(id)
+        .and_then(ElementRef::wrap)
+        .map_or_else(Vec::new, |element| {
+            element
+                .select(selector)
+                .map(|element| element.id())
+                .collect()
+        })
+}
+
+/// Returns the first descendant of the root matching the first of the selectors that matches any.
+fn find(html: &Html, root: NodeId, selectors: &[Selector]) -> Option<NodeId> {
+    selectors
+        .iter()
+        .find_map(|selector| select(html, root, selector).first().copied())
+}
+
+fn first_element_child(html: &Html, id: NodeId) -> Option<NodeId> {
+    html.tree
+        .get(id)?
+        .children()
+        .find(is_element)
+        .map(|child| child.id())
+}
+
+fn previous_element_sibling(html: &Html, id: NodeId) -> Option<NodeId> {
+    html.tree
+        .get(id)?
+        .prev_siblings()
+        .find(is_element)
+        .map(|sibling| sibling.id())
+}
+
+/// Sets the value of an attribute on an element.
+fn set_attribute(html: &mut Html, id: NodeId, name: &str, value: &str) {
+    if let Some(mut node) = html.tree.get_mut(id) {
+        if let Node::Element(element) = node.value() {
+            let name = QualName::new(None, ns!(), LocalName::from(name));
+            let value = StrTendril::from(value);
+            // Attributes are kept sorted by name, which `scraper` relies on when looking them up.
+            match element.attrs.binary_search_by(|(key, _)| key.cmp(&name)) {
+                Ok(index) => element.attrs[index].1 = value,
+                Err(index) => element.attrs.insert(index, (name, value)),
+            }
+        }
+    }
+}
+
+/// Removes an attribute from an element, returning its value.
+fn remove_attribute(html: &mut Html, id: NodeId, name: &str) -> Option<String> {
+    let mut node = html.tree.get_mut(id)?;
+    if let Node::Element(element) = node.value() {
+        let index = element
+            .attrs
+            .iter()
+            .position(|(key, _)| &*key.local == name)?;
+        Some(element.attrs.remove(index).1.to_string())
+    } else {
+        None
+    }
+}
+
+/// Trims the text node, if it is one.
+fn trim_text(html: &mut Html, id: Option<NodeId>, trim: fn(&str) -> &str) {
+    if let Some(mut node) = id.and_then(|id| html.tree.get_mut(id)) {
+        if let Node::Text(text) = node.value() {
+            text.text = StrTendril::from(trim(&text.text));
+        }
+    }
+}
+
+/// Replaces each paragraph within the docbox with its contents, and trims the surrounding
+/// whitespace, matching the layout of rustdoc's own docboxes.
+fn unwrap_paragraphs(html: &mut Html, stab: NodeId, selectors: &Selectors) -> Option<()> {
+    for paragraph in select(html, stab, &selectors.paragraph) {
+        while let Some(child) = html
+            .tree
+            .get(paragraph)?
+            .first_child()
+            .map(|child| child.id())
+        {
+            html.tree.get_mut(paragraph)?.insert_id_before(child);
+        }
+        html.tree.get_mut(paragraph)?.detach();
+    }
+    let first = html.tree.get(stab)?.first_child().map(|child| child.id());
+    trim_text(html, first, str::trim_start);
+    let last = html.tree.get(stab)?.last_child().map(|child| child.id());
+    trim_text(html, last, str::trim_end);
+    Some(())
+}
+
+/// Places markup within a module list summary.
+fn place_in_summary(
+    html: &mut Html,
+    element: NodeId,
+    kind: &str,
+    selectors: &Selectors,
+) -> Option<()> {
+    let parent = html.tree.get(element)?.parent()?;
+    let name = previous_element_sibling(html, parent.id()).filter(|&sibling| {
+        selectors.rows.iter().any(|(summary, name)| {
+            matches(parent, summary)
+                && html
+                    .tree
+                    .get(sibling)
+                    .map_or(false, |sibling| matches(sibling, name))
+        })
+    });
+    match kind {
+        "short-docbox" => {
+            html.tree.get_mut(name?)?.append_id(element);
+            return Some(());
+        }
+        "semi-transparent" => {
+            if let Some(name) = name {
+                let class = ElementRef::wrap(html.tree.get(name)?)?
+                    .attr("class")
+                    .unwrap_or_default();
+                let class = format!("{class} unstable").trim_start().to_owned();
+                set_attribute(html, name, "class", &class);
+                set_attribute(html, name, "style", "opacity:0.65");
+            }
+        }
+        _ => {}
+    }
+    html.tree.get_mut(element)?.detach();
+    Some(())
+}
+
+/// Places markup within an item's documentation.
+fn place_in_docblock(
+    html: &mut Html,
+    element: NodeId,
+    kind: &str,
+    docblock: NodeId,
+    selectors: &Selectors,
+) -> Option<()> {
+    let toggle = html.tree.get(docblock)?.parent()?;
+    let top_doc = matches(toggle, &selectors.top_doc);
+    let toggle = toggle.id();
+    match kind {
+        // Docboxes are moved out of the docblock and into the item-info position.
+        "docbox" => {
+            unwrap_paragraphs(html, first_element_child(html, element)?, selectors)?;
+            let position = if top_doc { toggle } else { docblock };
+            html.tree.get_mut(position)?.insert_id_before(element);
+        }
+        // Since values are moved to the right of the item's definition.
+        "since" => {
+            let mut target = if top_doc {
+                let page = html.tree.get(toggle)?.parent()?.id();
+                find(html, page, &selectors.headings)?
+            } else {
+                find(html, toggle, &selectors.rightsides)?
+            };
+            // Newer versions of rustdoc make the source link itself the right side, which must
+            // not contain the since value.
+            if matches(html.tree.get(target)?, &selectors.link) {
+                let wrapper = Html::parse_fragment("<span class='rightside'></span>")
+                    .root_element()
+                    .first_child()
+                    .map(|span| span.value().clone())?;
+                let wrapper = html.tree.orphan(wrapper).id();
+                if let Some(class) = ElementRef::wrap(html.tree.get(target)?)?.attr("class") {
+                    let class = class
+                        .split_whitespace()
+                        .filter(|name| *name != "rightside")
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    set_attribute(html, target, "class", &class);
+                }
+                html.tree.get_mut(target)?.insert_id_before(wrapper);
+                html.tree.get_mut(wrapper)?.append_id(target);
+                target = wrapper;
+            }
+            let since = *select(html, element, &selectors.since).first()?;
+            let separator = html
+                .tree
+                .orphan(Node::Text(Text {
+                    text: StrTendril::from(" \u{b7} "),
+                }))
+                .id();
+            html.tree.get_mut(target)?.prepend_id(separator);
+            html.tree.get_mut(target)?.prepend_id(since);
+            html.tree.get_mut(element)?.detach();
+        }
+        // Module list markup isn't displayed on the item's own page.
+        _ => {
+            html.tree.get_mut(element)?.detach();
+        }
+    }
+    Some(())
+}
+
+/// Places a single marked element, returning `None` if the surrounding layout isn't recognized, in
+/// which case the element is left where it is.
+fn place(html: &mut Html, element: NodeId, kind: &str, selectors: &Selectors) -> Option<()> {
+    // Module list summaries are the only documentation rendered outside of a docblock.
+    let docblock = html
+        .tree
+        .get(element)?
+        .ancestors()
+        .find(|&ancestor| matches(ancestor, &selectors.docblock))
+        .map(|docblock| docblock.id());
+    match docblock {
+        Some(docblock) => place_in_docblock(html, element, kind, docblock, selectors),
+        None => place_in_summary(html, element, kind, selectors),
+    }
+}
+
+/// Places the marked elements within a page, removes the bootstrap, and moves a single copy of each
+/// stylesheet into the page's head.
+///
+/// Returns the kinds of any elements that couldn't be placed.
+fn process_page(html: &mut Html, selectors: &Selectors) -> Vec<String> {
+    // Only the nodes attached to the document are selected, skipping any that have been detached.
+    let root = html.root_element().id();
+
+    let mut unplaced = Vec::new();
+    for element in select(html, root, &selectors.marked) {
+        let kind = remove_attribute(html, element, MARKER).unwrap();
+        if place(html, element, &kind, selectors).is_none() {
+            unplaced.push(kind);
+        }
+    }
+
+    for bootstrap in select(html, root, &selectors.bootstrap) {
+        if let Some(mut bootstrap) = html.tree.get_mut(bootstrap) {
+            bootstrap.detach();
+        }
+    }
+
+    let head = select(html, root, &selectors.head).first().copied();
+    let mut hrefs = HashSet::new();
+    for link in select(html, root, &selectors.styles) {
+        let href = html
+            .tree
+            .get(link)
+            .and_then(ElementRef::wrap)
+            .and_then(|link| link.attr("href"))
+            .map(str::to_owned);
+        match head.and_then(|head| html.tree.get_mut(head)) {
+            Some(mut head) if hrefs.insert(href) => {
+                head.append_id(link);
+            }
+            _ => {
+                if let Some(mut link) = html.tree.get_mut(link) {
+                    link.detach();
+                }
+            }
+        }
+    }
+
+    unplaced
+}
+
+/// Returns the paths of all HTML files within the directory.
+fn html_files(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(html_files(&path)?);
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension == "html")
+        {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn run(directory: &Path) -> Result<(), String> {
+    let selectors = &Selectors::new();
+    let files = html_files(directory)
+        .map_err(|err| format!("unable to read `{}`: {err}", directory.display()))?;
+    for path in files {
+        let html = fs::read_to_string(&path)
+            .map_err(|err| format!("unable to read `{}`: {err}", path.display()))?;
+        // Pages without any markup are left untouched.
+        if !html.contains(MARKER) {
+            continue;
+        }
+        let mut document = Html::parse_document(&html);
+        for kind in process_page(&mut document, selectors) {
+            eprintln!(
+                "warning: unable to place `{kind}` markup in `{}`",
+                path.display()
+            );
+        }
+        fs::write(&path, document.html())
+            .map_err(|err| format!("unable to write `{}`: {err}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let mut args = env::args_os().skip(1);
+    let directory = args
+        .next()
+        .map_or_else(|| PathBuf::from("target/doc"), PathBuf::from);
+    if args.next().is_some() {
+        eprintln!("usage: doc_item-postprocess [DIRECTORY]");
+        process::exit(2);
+    }
+
+    if let Err(err) = run(&directory) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,192 @@
+//! The scripts that place the HTML generated by each attribute.
+//!
+//! Each attribute only emits markup, marked using a `data-doc-item` attribute naming what kind of
+//! markup it is. A bootstrap script relocates the marked elements. rustdoc provides no way for a
+//! crate to include a script once on every page, so a copy of the bootstrap is included with each
+//! item's markup, at most once per item. Each copy is a small script that runs as soon as it is
+//! loaded and places the markup preceding it, keeping the generated HTML smaller than placing each
+//! piece of markup with its own script.
+//!
+//! There are two bootstraps, so that module lists only include what they need. The list bootstrap
+//! places short docboxes and semi-transparency, and is included after that markup within the
+//! item's summary. The page bootstrap places docboxes and since values, and is included after all
+//! of that markup at the end of the item's documentation, which is only displayed on the item's own
+//! page.
+//!
+//! The bootstraps are loaded from `data:` URLs, so that no script text is included within the
+//! documentation itself. Otherwise, it would appear in module summaries and in the search index.
+//!
+//! The stylesheets used by the markup, such as those of the built-in classes it uses, are included
+//! alongside the bootstraps in the same way, each as a `<link>` to a `data:` URL, and are left
+//! where they are. This works without scripts.
+
+use crate::{
+    layouts::{BOOTSTRAP, HEADINGS, MARKER, RIGHTSIDES, ROWS, STYLES},
+    metadata::Metadata,
+    palette,
+};
+
+/// Returns the distinct selectors, joined into a selector list.
+fn selector_list<'a>(selectors: impl Iterator<Item = &'a str>) -> String {
+    let mut distinct = Vec::new();
+    for selector in selectors {
+        if !distinct.contains(&selector) {
+            distinct.push(selector);
+        }
+    }
+    distinct.join(",")
+}
+
+/// Returns the property of an element's `dataset` corresponding to the marker attribute.
+fn marker_property() -> String {
+    let mut words = MARKER.trim_start_matches("data-").split('-');
+    let first = words.next().unwrap_or_default().to_owned();
+    words.fold(first, |property, word| {
+        let mut chars = word.chars();
+        let capitalized = chars
+            .next()
+            .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+            .unwrap_or_default();
+        property + &capitalized
+    })
+}
+
+/// Returns an expression finding the first element within `h` matching any of the selectors, in
+/// order of preference.
+fn find(selectors: &[&str]) -> String {
+    let queries = selectors
+        .iter()
+        .map(|selector| format!("h.querySelector(\"{selector}\")"))
+        .collect::<Vec<_>>();
+    queries.join("||")
+}
+
+/// Returns the list bootstrap, written compactly as a copy is included in the summary of every
+/// item using it. It runs as soon as it is loaded, placing the markup preceding it within its
+/// `p`arent. Its other variables are the `n`ame cell preceding the parent in module lists, and
+/// whether the parent is in a known module list `r`ow.
+fn list_script() -> String {
+    format!(
+        concat!(
+            "let s=document.currentScript,p=s.parentElement,n=p.previousElementSibling,",
+            "r=p.matches(\"{summaries}\")&&n&&n.matches(\"{names}\");s.remove();",
+            // Markup outside of known module list rows, such as on the item's own page, is removed.
+            "for(let e of p.querySelectorAll(\"[{marker}]\"))e.dataset.{property}==\"short-docbox\"&&r",
+            "?(delete e.dataset.{property},n.append(e))",
+            ":(r&&(n.classList.add(\"unstable\"),n.style.opacity=.65),e.remove())",
+        ),
+        marker = MARKER,
+        property = marker_property(),
+        summaries = selector_list(ROWS.iter().map(|(summary, _)| *summary)),
+        names = selector_list(ROWS.iter().map(|(_, name)| *name)),
+    )
+}
+
+/// Returns the page bootstrap, written compactly as a copy is included with every item using it.
+/// It runs as soon as it is loaded, placing the markup preceding it within its `r`oot docblock, and
+/// is therefore included after all of the markup appended to the item's documentation. Its other
+/// variables are the `e`lement being placed, the `t`oggle containing the docblock, whether the
+/// docblock is the `o`verall documentation of the page's item, the element `h`olding the item's
+/// heading or definition, and the `x` target the element is placed relative to.
+fn page_script() -> String {
+    format!(
+        concat!(
+            // Outside of a docblock, the bootstrap is its own root, within which nothing is placed.
+            "let s=document.currentScript,r=s.closest(\".docblock\")||s,t=r.parentElement,",
+            "o=t.matches(\".top-doc\"),h=o?t.parentElement:t,x,w;s.remove();",
+            "for(let e of r.querySelectorAll(\"[{marker}=docbox],[{marker}=since]\")){{",
+            "x=e.firstElementChild;",
+            // Docboxes are moved out of the docblock and into the item-info position, with the
+            // paragraphs rendered around their content removed.
+            "if(e.dataset.{property}==\"docbox\")",
+            "x.innerHTML=x.innerHTML.replace(/<\\/?p>/g,\"\").trim(),(o?t:r).before(e);",
+            // Since values are moved to the right of the item's definition. Those that can't be
+            // placed in a recognized layout are left where they are.
+            "else if(x=o?{headings}:{rightsides}){{",
+            // Newer versions of rustdoc make the source link itself the right side, which must not
+            // contain the since value.
+            "if(x.tagName==\"A\")w=document.createElement(\"span\"),w.className=\"rightside\",",
+            "x.classList.remove(\"rightside\"),x.before(w),w.append(x),x=w;",
+            "x.prepend(e.querySelector(\".since\"),\" \\xb7 \");e.remove()}}",
+            "delete e.dataset.{property}}}",
+        ),
+        marker = MARKER,
+        property = marker_property(),
+        headings = find(HEADINGS),
+        rightsides = find(RIGHTSIDES),
+    )
+}
+
+/// The classes styled by rustdoc itself.
+const RUSTDOC_CLASSES: [&str; 3] = ["unstable", "portability", "deprecated"];
+
+/// Hides short docboxes on the item's own page, where they would otherwise be displayed along with
+/// the item's summary. Module list summaries are not within a docblock.
+///
+/// Nothing else hides them without scripts, so they remain displayed wherever the stylesheet is
+/// blocked or styles aren't supported.
+const NO_JS_STYLES: &str = ".docblock [data-doc-item=short-docbox]{display:none}";
+
+/// Encodes the text as the body of a `data:` URL within a single-quoted HTML attribute.
+///
+/// Only the characters that would otherwise end the attribute, begin a character reference, or be
+/// treated specially by the URL are encoded, keeping the copies included with each item small.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| {
+            if byte.is_ascii_graphic() && !b"'&%#".contains(&byte) || byte == b' ' {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// Returns the HTML for a `<link>` to the stylesheet.
+fn stylesheet(css: &str) -> String {
+    format!(
+        "<link rel='stylesheet' {STYLES} href='data:text/css,{}'>",
+        encode(css)
+    )
+}
+
+/// Returns the HTML for a copy of each stylesheet used by markup with the given classes and inline
+/// style, followed by a copy of a bootstrap. `list` indicates whether the markup is placed in
+/// module lists, in which case the list bootstrap is used rather than the page bootstrap.
+///
+/// The bootstrap runs as soon as it is loaded, so it must follow the markup it places. It is
+/// omitted if scripts are disabled.
+pub fn assets(metadata: &Metadata, class: &str, style: &str, list: bool) -> Vec<String> {
+    let mut assets = Vec::new();
+    let built_in = palette::used(class, style);
+    for class in &built_in {
+        assets.push(stylesheet(&palette::css(class)));
+    }
+    if metadata.no_js && list {
+        assets.push(stylesheet(NO_JS_STYLES));
+    }
+    // The crate's styles may define its own classes or override the variables of the built-in
+    // classes.
+    if let Some(crate_styles) = &metadata.styles {
+        if !built_in.is_empty()
+            || class
+                .split_ascii_whitespace()
+                .any(|class| !RUSTDOC_CLASSES.contains(&class))
+        {
+            assets.push(stylesheet(crate_styles));
+        }
+    }
+    if !metadata.no_js {
+        assets.push(format!(
+            "<script {BOOTSTRAP} src='data:text/javascript,{}'></script>",
+            encode(&if list { list_script() } else { page_script() })
+        ));
+    }
+    assets
+}
+
+/// Returns whether the asset is a copy of a bootstrap, rather than of a stylesheet.
+pub fn is_bootstrap(asset: &str) -> bool {
+    asset.starts_with("<script")
+}
@@ -0,0 +1,409 @@
+//! Parsing and rendering of `cfg` predicates.
+//!
+//! Predicates are rendered into human-readable text the same way rustdoc renders the predicates
+//! given to `#[doc(cfg(...))]` in its portability boxes.
+
+use crate::{html, markdown};
+use std::fmt::{self, Display, Formatter};
+use syn::{Lit, Meta, NestedMeta};
+
+/// A `cfg` predicate.
+pub enum Cfg {
+    /// Always true, such as `all()`.
+    True,
+    /// Always false, such as `any()`.
+    False,
+    /// A single option, such as `unix` or `feature = "foo"`.
+    Option(String, Option<String>),
+    /// The negation of a predicate.
+    Not(Box<Self>),
+    /// True if any of the predicates are true.
+    Any(Vec<Self>),
+    /// True if all of the predicates are true.
+    All(Vec<Self>),
+}
+
+/// The format used when rendering a predicate.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Format {
+    /// Used within the docbox, as Markdown.
+    LongMarkdown,
+    /// Used within the `title` of the short docbox.
+    LongPlain,
+    /// Used within the short docbox.
+    ShortHtml,
+}
+
+impl Format {
+    /// Escapes text for use in this format.
+    fn escape(self, text: &str) -> String {
+        match self {
+            Self::LongMarkdown => markdown::escape_text(text),
+            Self::LongPlain | Self::ShortHtml => html::escape(text),
+        }
+    }
+
+    /// Formats text as code.
+    fn code(self, text: &str) -> String {
+        match self {
+            Self::LongMarkdown => markdown::code_span(text),
+            Self::LongPlain => format!("`{}`", html::escape(text)),
+            Self::ShortHtml => format!("<code>{}</code>", html::escape(text)),
+        }
+    }
+}
+
+impl Cfg {
+    /// Parses a predicate from a nested meta item, as would be given to `#[cfg(...)]`.
+    pub fn parse(nested_meta: &NestedMeta) -> darling::Result<Self> {
+        let meta = match nested_meta {
+            NestedMeta::Meta(meta) => meta,
+            NestedMeta::Lit(lit) => {
+                return Err(darling::Error::unexpected_lit_type(lit));
+            }
+        };
+        let name = meta
+            .path()
+            .get_ident()
+            .ok_or_else(|| {
+                darling::Error::custom("expected a `cfg` option name").with_span(meta.path())
+            })?
+            .to_string();
+        match meta {
+            Meta::Path(_) => Ok(Self::Option(name, None)),
+            Meta::NameValue(name_value) => match &name_value.lit {
+                Lit::Str(value) => Ok(Self::Option(name, Some(value.value()))),
+                lit => Err(darling::Error::unexpected_lit_type(lit)),
+            },
+            Meta::List(list) => {
+                let mut errors = darling::Error::accumulator();
+                let sub_cfgs = list
+                    .nested
+                    .iter()
+                    .filter_map(|nested_meta| errors.handle(Self::parse(nested_meta)))
+                    .collect::<Vec<_>>();
+                errors.finish()?;
+                match name.as_str() {
+                    "all" => Ok(Self::all(sub_cfgs)),
+                    "any" => Ok(Self::any(sub_cfgs)),
+                    "not" => {
+                        if sub_cfgs.len() == 1 {
+                            Ok(Self::Not(Box::new(sub_cfgs.into_iter().next().unwrap())))
+                        } else {
+                            Err(
+                                darling::Error::custom("`not` expects exactly one predicate")
+                                    .with_span(list),
+                            )
+                        }
+                    }
+                    _ => Err(darling::Error::custom(format!(
+                        "unknown `cfg` predicate `{name}`, expected `all`, `any`, or `not`"
+                    ))
+                    .with_span(&list.path)),
+                }
+            }
+        }
+    }
+
+    fn all(mut sub_cfgs: Vec<Self>) -> Self {
+        match sub_cfgs.len() {
+            0 => Self::True,
+            1 => sub_cfgs.pop().unwrap(),
+            _ => Self::All(sub_cfgs),
+        }
+    }
+
+    fn any(mut sub_cfgs: Vec<Self>) -> Self {
+        match sub_cfgs.len() {
+            0 => Self::False,
+            1 => sub_cfgs.pop().unwrap(),
+            _ => Self::Any(sub_cfgs),
+        }
+    }
+
+    /// Whether the predicate is rendered without needing parentheses.
+    fn is_simple(&self) -> bool {
+        match self {
+            Self::True | Self::False | Self::Option(..) => true,
+            Self::Not(sub_cfg) => sub_cfg.is_simple(),
+            Self::Any(_) | Self::All(_) => false,
+        }
+    }
+
+    /// Whether the predicate binds at least as tightly as `all`.
+    const fn is_all(&self) -> bool {
+        match self {
+            Self::True | Self::False | Self::Option(..) | Self::All(_) => true,
+            Self::Not(_) | Self::Any(_) => false,
+        }
+    }
+
+    fn should_capitalize_first_letter(&self) -> bool {
+        match self {
+            Self::True | Self::False | Self::Not(_) => true,
+            Self::Any(sub_cfgs) | Self::All(sub_cfgs) => sub_cfgs
+                .first()
+                .map_or(false, Self::should_capitalize_first_letter),
+            Self::Option(name, _) => name == "debug_assertions" || name == "target_endian",
+        }
+    }
+
+    fn should_append_only_to_description(&self) -> bool {
+        match self {
+            Self::True | Self::False => false,
+            Self::Any(_) | Self::All(_) | Self::Option(..) => true,
+            Self::Not(sub_cfg) => matches!(**sub_cfg, Self::Option(..)),
+        }
+    }
+
+    fn should_use_with_in_description(&self) -> bool {
+        matches!(self, Self::Option(name, _) if name == "target_feature")
+    }
+
+    fn preposition(&self) -> &'static str {
+        if self.should_use_with_in_description() {
+            "with"
+        } else {
+            "on"
+        }
+    }
+
+    fn only(&self) -> &'static str {
+        if self.should_append_only_to_description() {
+            " only"
+        } else {
+            ""
+        }
+    }
+
+    /// Renders the predicate as the Markdown contents of a docbox.
+    pub fn render_long_markdown(&self) -> String {
+        format!(
+            "Available {} **{}**{}.",
+            self.preposition(),
+            Render(self, Format::LongMarkdown),
+            self.only()
+        )
+    }
+
+    /// Renders the predicate as plain text, for use as a tooltip.
+    pub fn render_long_plain(&self) -> String {
+        format!(
+            "Available {} {}{}",
+            self.preposition(),
+            Render(self, Format::LongPlain),
+            self.only()
+        )
+    }
+
+    /// Renders the predicate as the contents of a short docbox.
+    pub fn render_short_html(&self) -> String {
+        let mut short = Render(self, Format::ShortHtml).to_string();
+        if self.should_capitalize_first_letter() {
+            if let Some(first) = short.chars().next() {
+                short.replace_range(..first.len_utf8(), &first.to_uppercase().to_string());
+            }
+        }
+        short
+    }
+
+    /// Renders the predicate as Rust source, as would be given to `#[cfg(...)]`.
+    pub fn predicate(&self) -> String {
+        match self {
+            Self::True => "all()".to_owned(),
+            Self::False => "any()".to_owned(),
+            Self::Option(name, None) => name.clone(),
+            Self::Option(name, Some(value)) => format!("{name} = {value:?}"),
+            Self::Not(sub_cfg) => format!("not({})", sub_cfg.predicate()),
+            Self::Any(sub_cfgs) => format!(
+                "any({})",
+                sub_cfgs
+                    .iter()
+                    .map(Self::predicate)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::All(sub_cfgs) => format!(
+                "all({})",
+                sub_cfgs
+                    .iter()
+                    .map(Self::predicate)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Renders a predicate in the given format.
+struct Render<'a>(&'a Cfg, Format);
+
+impl Render<'_> {
+    fn write_with_optional_parentheses(
+        &self,
+        formatter: &mut Formatter,
+        sub_cfg: &Cfg,
+        parenthesize: bool,
+    ) -> fmt::Result {
+        if parenthesize {
+            write!(formatter, "({})", Render(sub_cfg, self.1))
+        } else {
+            write!(formatter, "{}", Render(sub_cfg, self.1))
+        }
+    }
+
+    fn fmt_option(
+        &self,
+        formatter: &mut Formatter,
+        name: &str,
+        value: Option<&str>,
+    ) -> fmt::Result {
+        let human_readable = match (name, value) {
+            ("target_endian", Some(endian)) => {
+                return write!(formatter, "{}-endian", self.1.escape(endian));
+            }
+            ("target_pointer_width", Some(bits)) => {
+                return write!(formatter, "{}-bit", self.1.escape(bits));
+            }
+            ("target_feature", Some(feature)) => {
+                return match self.1 {
+                    Format::ShortHtml => formatter.write_str(&self.1.code(feature)),
+                    _ => write!(formatter, "target feature {}", self.1.code(feature)),
+                };
+            }
+            ("feature", Some(feature)) => {
+                return match self.1 {
+                    Format::ShortHtml => formatter.write_str(&self.1.code(feature)),
+                    _ => write!(formatter, "crate feature {}", self.1.code(feature)),
+                };
+            }
+            _ => human_readable(name, value),
+        };
+        if !human_readable.is_empty() {
+            formatter.write_str(human_readable)
+        } else if let Some(value) = value {
+            formatter.write_str(&self.1.code(&format!("{name}=\"{value}\"")))
+        } else {
+            formatter.write_str(&self.1.code(name))
+        }
+    }
+}
+
+impl Display for Render<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            Cfg::Not(sub_cfg) => match &**sub_cfg {
+                Cfg::Any(sub_cfgs) => {
+                    let separator = if sub_cfgs.iter().all(Cfg::is_simple) {
+                        " nor "
+                    } else {
+                        ", nor "
+                    };
+                    for (i, sub_cfg) in sub_cfgs.iter().enumerate() {
+                        formatter.write_str(if i == 0 { "neither " } else { separator })?;
+                        self.write_with_optional_parentheses(
+                            formatter,
+                            sub_cfg,
+                            !sub_cfg.is_all(),
+                        )?;
+                    }
+                    Ok(())
+                }
+                simple @ Cfg::Option(..) => write!(formatter, "non-{}", Render(simple, self.1)),
+                sub_cfg => write!(formatter, "not ({})", Render(sub_cfg, self.1)),
+            },
+            Cfg::Any(sub_cfgs) => {
+                let separator = if sub_cfgs.iter().all(Cfg::is_simple) {
+                    " or "
+                } else {
+                    ", or "
+                };
+                for (i, sub_cfg) in sub_cfgs.iter().enumerate() {
+                    if i != 0 {
+                        formatter.write_str(separator)?;
+                    }
+                    self.write_with_optional_parentheses(formatter, sub_cfg, !sub_cfg.is_all())?;
+                }
+                Ok(())
+            }
+            Cfg::All(sub_cfgs) => {
+                for (i, sub_cfg) in sub_cfgs.iter().enumerate() {
+                    if i != 0 {
+                        formatter.write_str(" and ")?;
+                    }
+                    self.write_with_optional_parentheses(formatter, sub_cfg, !sub_cfg.is_simple())?;
+                }
+                Ok(())
+            }
+            Cfg::True => formatter.write_str("everywhere"),
+            Cfg::False => formatter.write_str("nowhere"),
+            Cfg::Option(name, value) => self.fmt_option(formatter, name, value.as_deref()),
+        }
+    }
+}
+
+/// Returns the human-readable name of a well-known configuration option, if there is one.
+fn human_readable(name: &str, value: Option<&str>) -> &'static str {
+    match (name, value) {
+        ("unix", None) => "Unix",
+        ("windows", None) => "Windows",
+        ("debug_assertions", None) => "debug-assertions enabled",
+        ("target_os", Some(os)) => match os {
+            "android" => "Android",
+            "dragonfly" => "DragonFly BSD",
+            "emscripten" => "Emscripten",
+            "freebsd" => "FreeBSD",
+            "fuchsia" => "Fuchsia",
+            "haiku" => "Haiku",
+            "hermit" => "HermitCore",
+            "illumos" => "illumos",
+            "ios" => "iOS",
+            "l4re" => "L4Re",
+            "linux" => "Linux",
+            "macos" => "macOS",
+            "netbsd" => "NetBSD",
+            "openbsd" => "OpenBSD",
+            "redox" => "Redox",
+            "solaris" => "Solaris",
+            "wasi" => "WASI",
+            "windows" => "Windows",
+            _ => "",
+        },
+        ("target_arch", Some(arch)) => match arch {
+            "aarch64" => "AArch64",
+            "arm" => "ARM",
+            "asmjs" => "JavaScript",
+            "m68k" => "M68k",
+            "mips" => "MIPS",
+            "mips64" => "MIPS-64",
+            "msp430" => "MSP430",
+            "powerpc" => "PowerPC",
+            "powerpc64" => "PowerPC-64",
+            "riscv32" => "RISC-V RV32",
+            "riscv64" => "RISC-V RV64",
+            "s390x" => "s390x",
+            "sparc64" => "SPARC64",
+            "wasm32" | "wasm64" => "WebAssembly",
+            "x86" => "x86",
+            "x86_64" => "x86-64",
+            _ => "",
+        },
+        ("target_vendor", Some(vendor)) => match vendor {
+            "apple" => "Apple",
+            "pc" => "PC",
+            "sun" => "Sun",
+            "fortanix" => "Fortanix",
+            _ => "",
+        },
+        ("target_env", Some(env)) => match env {
+            "gnu" => "GNU",
+            "msvc" => "MSVC",
+            "musl" => "musl",
+            "newlib" => "Newlib",
+            "uclibc" => "uClibc",
+            "sgx" => "SGX",
+            _ => "",
+        },
+        _ => "",
+    }
+}
@@ -0,0 +1,172 @@
+//! Utilities for generating HTML.
+
+/// Escapes text for use within HTML content or attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns whether the text is a valid CSS class name.
+///
+/// This follows the CSS grammar for identifiers, excluding escape sequences.
+pub fn is_class_name(text: &str) -> bool {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii();
+    let mut chars = text.chars();
+    let start = match chars.next() {
+        Some('-') => chars.clone().next(),
+        start => start,
+    };
+    match start {
+        Some(c) if c == '-' || (is_name_char(c) && !c.is_ascii_digit()) => chars.all(is_name_char),
+        _ => false,
+    }
+}
+
+/// Elements that never have any content, and therefore never have a closing tag.
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// A tag within HTML.
+struct Tag<'a> {
+    name: &'a str,
+    closing: bool,
+    self_closing: bool,
+}
+
+/// Parses the tag at the start of `html`, which must begin with `<`.
+///
+/// Returns the tag, if there is one, along with the length of the text that was consumed. Text that
+/// is not a tag, such as a lone `<`, consumes only the `<`.
+fn parse_tag(html: &str) -> Result<(Option<Tag<'_>>, usize), String> {
+    if let Some(comment) = html.strip_prefix("<!--") {
+        return comment
+            .find("-->")
+            .map(|end| (None, end + 7))
+            .ok_or_else(|| "unclosed comment".to_owned());
+    }
+    let closing = html[1..].starts_with('/');
+    let name_start = if closing { 2 } else { 1 };
+    let name_len = html[name_start..]
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(html.len() - name_start);
+    if !html[name_start..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Ok((None, 1));
+    }
+    let name = &html[name_start..name_start + name_len];
+
+    // Find the end of the tag, skipping over quoted attribute values.
+    let mut quote = None;
+    for (index, c) in html.char_indices().skip(name_start + name_len) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            (None, '>') => {
+                return Ok((
+                    Some(Tag {
+                        name,
+                        closing,
+                        self_closing: html[..index].ends_with('/'),
+                    }),
+                    index + 1,
+                ));
+            }
+            _ => {}
+        }
+    }
+    Err(format!("unterminated tag `<{}{name}`", if closing { "/" } else { "" }))
+}
+
+/// Checks that HTML is well-formed.
+///
+/// Every element must be closed in the order it was opened, void elements must not be closed, and
+/// other elements must not be self-closing. `<script>` elements are not allowed, as they would
+/// interfere with the scripts used to relocate the generated HTML.
+pub fn check(html: &str) -> Result<(), String> {
+    let mut open = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = html[position..].find('<') {
+        position += offset;
+        let (tag, len) = parse_tag(&html[position..])?;
+        position += len;
+        let tag = match tag {
+            Some(tag) => tag,
+            None => continue,
+        };
+        let name = tag.name.to_ascii_lowercase();
+        if name == "script" {
+            return Err(
+                "`<script>` tags are not allowed, as they would interfere with the scripts generated by `doc_item`"
+                    .to_owned(),
+            );
+        }
+        let void = VOID_ELEMENTS.contains(&name.as_str());
+        if tag.closing {
+            if void {
+                return Err(format!("void element `<{name}>` cannot have a closing tag"));
+            }
+            match open.pop() {
+                Some(expected) if expected == name => {}
+                Some(expected) => {
+                    return Err(format!(
+                        "mismatched closing tag `</{name}>`, expected `</{expected}>`"
+                    ))
+                }
+                None => return Err(format!("unexpected closing tag `</{name}>`")),
+            }
+        } else if tag.self_closing && !void {
+            return Err(format!(
+                "non-void element `<{name}>` cannot be self-closing, use `<{name}></{name}>` instead"
+            ));
+        } else if !void {
+            open.push(name);
+        }
+    }
+    open.pop()
+        .map_or(Ok(()), |name| Err(format!("unclosed tag `<{name}>`")))
+}
+
+/// Escapes HTML so that it is rendered unchanged when placed within a Markdown paragraph.
+///
+/// Tags, comments, and character references are left as-is, as Markdown passes them through.
+/// Every other ASCII punctuation character is backslash-escaped, so that no text, including the
+/// bodies of scripts, is interpreted as Markdown.
+pub fn escape_markdown(html: &str) -> String {
+    let mut escaped = String::with_capacity(html.len());
+    let mut position = 0;
+    while let Some(c) = html[position..].chars().next() {
+        let raw_len = match c {
+            '<' => match parse_tag(&html[position..]) {
+                Ok((_, len)) if len > 1 => len,
+                _ => 0,
+            },
+            '&' => html[position + 1..]
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+                .filter(|&len| len > 0 && html[position + 1 + len..].starts_with(';'))
+                .map_or(0, |len| len + 2),
+            _ => 0,
+        };
+        if raw_len > 0 {
+            escaped.push_str(&html[position..position + raw_len]);
+            position += raw_len;
+        } else {
+            if c.is_ascii_punctuation() {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            position += c.len_utf8();
+        }
+    }
+    escaped
+}
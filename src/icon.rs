@@ -0,0 +1,56 @@
+//! The bundled icons available to docboxes.
+//!
+//! Icons are inline SVG images drawn in the current text color. They are placed within an `emoji`
+//! span, the same as the emoji used by rustdoc's own item-info, and are sized to match the
+//! surrounding text, so that they are displayed the same way as an emoji would be.
+
+/// The names of the bundled icons, along with the path data for each, drawn on a 16x16 grid.
+const ICONS: [(&str, &str); 6] = [
+    (
+        "experimental",
+        "M5.5 1h5v1.5h-.75v3.6l3.9 6.6c.6 1.05-.15 2.3-1.35 2.3H3.7c-1.2 0-1.95-1.25-1.35-2.3l3.9-6.6V2.5H5.5zM7.75 2.5v4l-1.6 2.75h3.7L8.25 6.5v-4z",
+    ),
+    (
+        "warning",
+        "M8 1.2l7.2 13.3H.8zM7.2 6v4.2h1.6V6zm0 5.4V13h1.6v-1.6z",
+    ),
+    (
+        "lock",
+        "M4.5 7V5a3.5 3.5 0 0 1 7 0v2H13v8H3V7zM6 7h4V5a2 2 0 0 0-4 0z",
+    ),
+    (
+        "platform",
+        "M1 2h14v9H9v2h2v1.5H5V13h2v-2H1zm1.5 1.5v6h11v-6z",
+    ),
+    (
+        "feature",
+        "M5 4h6a4 4 0 0 1 0 8H5a4 4 0 0 1 0-8zm6 1.5a2.5 2.5 0 1 0 0 5 2.5 2.5 0 0 0 0-5z",
+    ),
+    (
+        "deprecated",
+        "M8 1a7 7 0 1 1 0 14A7 7 0 0 1 8 1zm0 1.5a5.5 5.5 0 0 0-4.3 8.9l7.7-7.7A5.5 5.5 0 0 0 8 2.5zm4.3 2.1l-7.7 7.7a5.5 5.5 0 0 0 7.7-7.7z",
+    ),
+];
+
+/// Returns the names of the bundled icons.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    ICONS.iter().map(|(name, _)| *name)
+}
+
+/// Returns whether the icon is meant to be one of the bundled icons, rather than text such as an
+/// emoji.
+pub fn is_name(icon: &str) -> bool {
+    !icon.is_empty()
+        && icon
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the SVG image for the bundled icon with the given name, if there is one.
+pub fn svg(name: &str) -> Option<String> {
+    ICONS.iter().find(|(icon, _)| *icon == name).map(|(_, path)| {
+        format!(
+            "<svg width='1em' height='1em' viewBox='0 0 16 16' fill='currentColor' fill-rule='evenodd' aria-hidden='true' style='vertical-align:-0.125em'><path d='{path}'></path></svg>"
+        )
+    })
+}
@@ -0,0 +1,24 @@
+//! The markup shared by the bootstraps and the `doc_item-postprocess` tool, along with the layouts
+//! of known versions of rustdoc that the markup is placed within.
+//!
+//! This module is included by both the library and the tool, so that the two place markup
+//! identically.
+
+/// The attribute marking markup to be relocated, naming what kind of markup it is.
+pub const MARKER: &str = "data-doc-item";
+/// The attribute identifying copies of the bootstraps.
+pub const BOOTSTRAP: &str = "data-doc-item-bootstrap";
+/// The attribute identifying copies of the stylesheets.
+pub const STYLES: &str = "data-doc-item-styles";
+
+/// Module list rows in the layouts of known versions of rustdoc, newest first, as pairs of the cell
+/// containing the item's summary and the cell containing its name.
+pub const ROWS: &[(&str, &str)] = &[
+    ("dd", "dt"),
+    (".docblock-short", ".item-name"),
+    (".docblock-short", ".module-item"),
+];
+/// The headings of item pages in known versions of rustdoc, newest first.
+pub const HEADINGS: &[&str] = &[".sub-heading", ".out-of-band"];
+/// The right sides of item definitions within pages in known versions of rustdoc, newest first.
+pub const RIGHTSIDES: &[&str] = &[".rightside"];
@@ -24,12 +24,54 @@
 //! ///
 //! /// The docbox will indicate the function is experimental. It will also appear semi-transparent on
 //! /// module lists.
-//! #[doc_item::docbox(content="<span class='emoji'>🔬</span> This is an experimental API.", class="unstable")]
+//! #[doc_item::unstable(feature="foo")]
+//! pub fn foo() {}
+//! ```
+//!
+//! The `unstable` attribute is shorthand for combining a docbox, a short docbox, and
+//! semi-transparency. The same result can be customized further by using the attributes directly:
+//!
+//! ```
+//! /// This is an experimental API.
+//! #[doc_item::docbox(content="This is an experimental API.", class="unstable", icon="🔬")]
 //! #[doc_item::short_docbox(content="Experimental", class="unstable")]
 //! #[doc_item::semi_transparent]
 //! pub fn foo() {}
 //! ```
 //!
+//! ### Documenting Configuration-Specific APIs
+//! Items that are only available on certain configurations can be annotated with their configuration
+//! predicate (similar to what is done on nightly through the
+//! [`doc_cfg`](https://doc.rust-lang.org/beta/unstable-book/language-features/doc-cfg.html) feature)
+//! as follows:
+//!
+//! ```
+//! /// This is only available when the `foo` feature is enabled.
+//! #[doc_item::doc_cfg(feature="foo", apply)]
+//! pub fn foo() {}
+//! ```
+//!
+//! The item's docbox will read "Available on **crate feature `foo`** only.", and the `apply` parameter
+//! conditionally compiles the item using the same predicate.
+//!
+//! ### Using the Built-In Classes
+//! Besides rustdoc's own `unstable`, `portability`, and `deprecated` classes, docboxes and short
+//! docboxes can use any of the classes `info`, `note`, `warning`, `danger`, `success`, and
+//! `security` without any additional styles:
+//!
+//! ```
+//! /// An item with a warning.
+//! #[doc_item::docbox(content="Calling this function twice will panic.", class="warning")]
+//! #[doc_item::short_docbox(content="Warning", class="warning")]
+//! pub fn foo() {}
+//! ```
+//!
+//! The classes have colors for each of rustdoc's light, dark, and ayu themes, and follow the theme
+//! selected by the reader. Each class's colors are defined by the
+//! `--doc-item-<class>-background` and `--doc-item-<class>-border` CSS variables, which can be
+//! overridden within a crate's own styles, described below.
+//! The colors of a class are only included in the documentation of items using it.
+//!
 //! ### Creating Custom-Styled Docboxes
 //! You can create your own custom styles to customize the display of docboxes. Define your item's
 //! docbox as follows:
@@ -43,28 +85,142 @@
 //! pub fn foo() {}
 //! ```
 //!
-//! Next, create a style definition in a separate HTML file.
-//! ```html
-//! <style>
-//!     .custom {
-//!         background: #c4ffd7;
-//!         border-color: #7bdba1;
-//!     }
-//! </style>
+//! Next, define the style using the [`styles!`] macro in your crate root:
+//!
+//! ```
+//! doc_item::styles!(".custom { background: #c4ffd7; border-color: #7bdba1; }");
+//! # fn main() {}
 //! ```
 //!
-//! Finally, include the HTML file's contents in your documentation:
+//! The styles are included with the documentation of each item using a custom class, whether the
+//! documentation is built by `cargo doc` or by [docs.rs](https://docs.rs/). No additional rustdoc
+//! flags are needed.
 //!
-//! ```bash
-//! $ RUSTDOCFLAGS="--html-in-header custom.html" cargo doc --no-deps --open
+//! As rustdoc provides no way to include a stylesheet once per page, this comes with limitations:
+//!
+//! - The CSS is not included once per page. A copy is included with each item using a custom
+//!   class, both on the item's own page and in each module list row summarizing it, so a module
+//!   listing many such items includes as many copies. The `doc_item-postprocess` tool reduces them
+//!   to a single copy per page.
+//! - The invocations are found by reading the crate root's source, which is never expanded. An
+//!   invocation's CSS is included even if a `#[cfg]` attribute disables the invocation, and
+//!   invocations generated by other macros are not found.
+//!
+//! For a one-off box, colors can instead be provided directly using the `background`, `border`, and
+//! `color` parameters. An `icon` can also be displayed before the content, either an emoji or one of
+//! the bundled icons: `experimental`, `warning`, `lock`, `platform`, `feature`, or `deprecated`.
+//!
+//! ```
+//! #[doc_item::docbox(content="A one-off docbox", background="#c4ffd7", border="#7bdba1", icon="🌱")]
+//! pub fn foo() {}
+//! ```
+//!
+//! ### Linking to Tracking Issues
+//! Docboxes can refer to an issue in your crate's issue tracker, in the same way the standard library
+//! refers to the tracking issues of its experimental APIs. Configure the URL of an issue in your
+//! `Cargo.toml`, with `{}` in place of the issue number:
+//!
+//! ```toml
+//! [package.metadata.doc_item]
+//! issue_url = "https://github.com/org/repo/issues/{}"
+//! ```
+//!
+//! Then provide the `issue` number:
+//!
+//! ```ignore
+//! #[doc_item::docbox(content="This is an experimental API.", class="unstable", issue=42)]
+//! pub fn foo() {}
+//! ```
+//!
+//! The docbox will read "This is an experimental API. (see issue #42)", linking to the issue. The
+//! `unstable` attribute's `issue` is linked the same way.
+//!
+//! ### Reusing Docboxes Through Presets
+//! Docboxes that appear throughout a crate can be defined once as presets in your `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.doc_item.presets.experimental]
+//! content = "This is an experimental API."
+//! short = "Experimental"
+//! class = "unstable"
+//! icon = "🔬"
+//! ```
+//!
+//! Then refer to the preset by name:
+//!
+//! ```ignore
+//! #[doc_item::docbox(preset="experimental")]
+//! #[doc_item::short_docbox(preset="experimental")]
+//! pub fn foo() {}
+//! ```
+//!
+//! Short docboxes use the preset's `short` text, falling back to its `content`. Any parameter
+//! provided by the attribute itself takes precedence over the preset's, so a preset can be adjusted
+//! for a single item:
+//!
+//! ```ignore
+//! #[doc_item::docbox(preset="experimental", content="This API is experimental on Windows.")]
+//! pub fn foo() {}
 //! ```
 //!
-//! And instruct [docs.rs](https://docs.rs/) to include the HTML file's contents as well by adding to your `Cargo.toml`:
+//! ### Placeholders
+//! The `content` of docboxes, short docboxes, and since values may refer to the crate and the
+//! annotated item using placeholders, which are expanded during compilation:
+//!
+//! ```
+//! /// This item is new.
+//! #[doc_item::docbox(content="Added to {crate} in {version}; see {kind} `{item}`.", class="info")]
+//! #[doc_item::since(content="{version}")]
+//! pub fn foo() {}
+//! ```
+//!
+//! The available placeholders are `{crate}`, `{version}`, `{item}`, and `{kind}`. Literal braces are
+//! written as `{{` and `}}`, and any other placeholder is a compile error.
+//!
+//! ### Rendering Without JavaScript
+//! By default, a small script places the generated markup where rustdoc places its own item-info,
+//! since values, and module list annotations. Documentation that must work without JavaScript, such
+//! as when it is served under a strict Content Security Policy or read in a text browser, can
+//! instead be generated as static markup by adding the following to your `Cargo.toml`:
 //!
 //! ```toml
-//! [package.metadata.docs.rs]
-//! rustdoc-args = [ "--html-in-header", "custom.html" ]
+//! [package.metadata.doc_item]
+//! no_js = true
 //! ```
+//!
+//! In this mode, docboxes and since values are displayed as boxes at the end of the item's
+//! documentation, and short docboxes are displayed at the start of the item's summary in module
+//! lists. Semi-transparency is not applied. On the item's own page, short docboxes are hidden by a
+//! stylesheet loaded from a `data:` URL. Where that stylesheet is blocked, such as by a Content
+//! Security Policy not allowing `data:` styles, or where styles aren't supported, such as in text
+//! browsers, short docboxes are also displayed at the start of the item's summary on its own page.
+//!
+//! Alternatively, the markup can be placed ahead of time by the `doc_item-postprocess` tool, which
+//! rewrites the generated documentation to the same layout the script produces and removes the
+//! script. As rustdoc provides no way to include a script once per page, a copy of the script is
+//! otherwise included with each item using the attributes, which the tool also removes. Install it
+//! and run it after generating the documentation:
+//!
+//! ```bash
+//! $ cargo install doc_item --features postprocess
+//! $ cargo doc --no-deps
+//! $ doc_item-postprocess target/doc
+//! ```
+//!
+//! ### Documentation Outside of the Browser
+//! The HTML generated by these attributes is meant for rustdoc's HTML output. Tools that
+//! display an item's documentation as Markdown, such as IDE hovers and rustdoc's JSON output, are given
+//! a readable Markdown form instead: docboxes become block quotes, and since values become a short note
+//! such as *Since 1.2.0*. Short docboxes and semi-transparency only affect module lists, and are
+//! omitted.
+//!
+//! The HTML is always included, unless the documentation is known to be read as Markdown: rustdoc
+//! generating its JSON output, or rust-analyzer displaying an IDE hover. This is detected from the
+//! arguments of the process running the attributes, and is best-effort. Any other tool is given the
+//! HTML, whose scripts and stylesheets are stored as `data:` URLs, so that tools rendering it don't
+//! display their code as text. Items documented from a dependency's compiled metadata, such as items
+//! re-exported from another crate using `#[doc(inline)]`, always have the HTML, and are displayed by
+//! rustdoc the same as any other item.
 
 #![warn(
     clippy::cargo,
@@ -76,23 +232,804 @@
 
 extern crate proc_macro;
 
-use darling::FromMeta;
-use proc_macro::{token_stream, TokenStream};
-use std::str::FromStr;
-use syn::{parse_macro_input, AttributeArgs};
+mod bootstrap;
+mod cfg;
+mod css;
+mod html;
+mod icon;
+mod layouts;
+mod markdown;
+mod metadata;
+mod palette;
+mod placeholder;
+mod splice;
+mod styles;
+
+use cfg::Cfg;
+use darling::{util::SpannedValue, FromMeta};
+use metadata::Metadata;
+use proc_macro::{token_stream, Delimiter, Group, Literal, Span, TokenStream, TokenTree};
+use semver::Version;
+use splice::Spliced;
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    path::Path,
+    rc::Rc,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use syn::{
+    parse::Parser, parse_macro_input, Attribute, AttributeArgs, Lit, LitStr, Meta, MetaNameValue,
+    NestedMeta,
+};
 
 #[derive(FromMeta)]
 struct BoxArgs {
     #[darling(default)]
-    content: String,
+    content: Option<LitStr>,
+    #[darling(default)]
+    content_file: Option<SpannedValue<String>>,
+    #[darling(default)]
+    class: SpannedValue<String>,
     #[darling(default)]
-    class: String,
+    markdown: Option<bool>,
+    #[darling(default)]
+    raw_html: Option<bool>,
+    #[darling(default)]
+    background: Option<SpannedValue<String>>,
+    #[darling(default)]
+    border: Option<SpannedValue<String>>,
+    #[darling(default)]
+    color: Option<SpannedValue<String>>,
+    #[darling(default)]
+    icon: Option<SpannedValue<String>>,
+    #[darling(default)]
+    issue: Option<SpannedValue<u32>>,
+    #[darling(default)]
+    preset: Option<SpannedValue<String>>,
+    /// The resolved path of the `content_file`, once it has been read.
+    #[darling(skip)]
+    content_path: Option<String>,
+    /// The macro call providing the `content`, if any, taken out by [`splice::extract`].
+    #[darling(skip)]
+    content_call: Option<TokenStream>,
+}
+
+impl BoxArgs {
+    fn content(&self) -> String {
+        self.content
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_default()
+    }
+
+    fn markdown(&self) -> bool {
+        self.markdown.unwrap_or(true)
+    }
+
+    fn raw_html(&self) -> bool {
+        self.raw_html.unwrap_or(false)
+    }
+
+    /// Returns the HTML for the box's icon, followed by a space, if it has one.
+    fn icon_html(&self) -> String {
+        self.icon.as_ref().map_or_else(String::new, |icon| {
+            format!(
+                "<span class='emoji'>{}</span> ",
+                icon::svg(icon).unwrap_or_else(|| html::escape(icon))
+            )
+        })
+    }
+
+    /// Returns the HTML referring to the box's tracking issue, preceded by a space, if it has one.
+    fn issue_html(&self, metadata: &Metadata) -> String {
+        self.issue.as_ref().map_or_else(String::new, |issue| {
+            format!(" (see issue {})", issue_link_html(metadata, **issue))
+        })
+    }
+
+    /// Uses the contents of the box's `content_file` as its content, if it has one.
+    ///
+    /// The contents are given the span of the `content_file` parameter, and are otherwise treated
+    /// exactly as if they were provided by `content`. The resolved path is kept, so that the file
+    /// can be tracked by the compiler using [`track_file`].
+    fn read_content_file(&mut self) -> darling::Result<()> {
+        let path = match &self.content_file {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if self.content.is_some() {
+            return Err(darling::Error::custom(
+                "`content` and `content_file` cannot both be provided",
+            )
+            .with_span(path));
+        }
+        let resolved = metadata::resolve(path);
+        let contents = fs::read_to_string(&resolved).map_err(|err| {
+            darling::Error::custom(format!("unable to read `{}`: {err}", path.as_str()))
+                .with_span(path)
+        })?;
+        let resolved = resolved.into_os_string().into_string().map_err(|_| {
+            darling::Error::custom(format!(
+                "the path of `{}` is not valid UTF-8",
+                path.as_str()
+            ))
+            .with_span(path)
+        })?;
+        // Files usually end with a newline, which isn't part of the content.
+        self.content = Some(LitStr::new(contents.trim_end(), path.span()));
+        self.content_path = Some(resolved);
+        Ok(())
+    }
+
+    /// Fills in the parameters not provided by the attribute from the box's preset, if it has one.
+    ///
+    /// Short docboxes use the preset's `short` text as their content, falling back to its
+    /// `content` or `content_file`. The preset's content is only used if the attribute provides
+    /// neither `content` nor `content_file`. Values taken from the preset are attributed to the
+    /// `preset` parameter's span.
+    fn apply_preset(&mut self, metadata: &Metadata, short: bool) -> darling::Result<()> {
+        let name = match &self.preset {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+        let preset = metadata.presets.get(name.as_str()).ok_or_else(|| {
+            let message = if metadata.presets.is_empty() {
+                format!(
+                    "unknown preset `{}`, as no presets are defined in the `[package.metadata.doc_item.presets]` table of `Cargo.toml`",
+                    name.as_str()
+                )
+            } else {
+                format!(
+                    "unknown preset `{}`, expected one of {}",
+                    name.as_str(),
+                    metadata
+                        .presets
+                        .keys()
+                        .map(|name| format!("`{name}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            darling::Error::custom(message).with_span(name)
+        })?;
+        let span = name.span();
+        let spanned = |value: &String| SpannedValue::new(value.clone(), span);
+
+        if self.content.is_none() && self.content_file.is_none() {
+            if let Some(short) = preset.short.as_ref().filter(|_| short) {
+                self.content = Some(LitStr::new(short, span));
+            } else {
+                self.content = preset
+                    .content
+                    .as_ref()
+                    .map(|content| LitStr::new(content, span));
+                self.content_file = preset.content_file.as_ref().map(spanned);
+            }
+        }
+        if self.class.is_empty() {
+            if let Some(class) = &preset.class {
+                self.class = spanned(class);
+            }
+        }
+        self.markdown = self.markdown.or(preset.markdown);
+        self.raw_html = self.raw_html.or(preset.raw_html);
+        self.background = self
+            .background
+            .take()
+            .or_else(|| preset.background.as_ref().map(spanned));
+        self.border = self
+            .border
+            .take()
+            .or_else(|| preset.border.as_ref().map(spanned));
+        self.color = self
+            .color
+            .take()
+            .or_else(|| preset.color.as_ref().map(spanned));
+        self.icon = self
+            .icon
+            .take()
+            .or_else(|| preset.icon.as_ref().map(spanned));
+        self.issue = self
+            .issue
+            .take()
+            .or_else(|| preset.issue.map(|issue| SpannedValue::new(issue, span)));
+        Ok(())
+    }
+
+    /// Expands the placeholders within the box's content, unless it is provided by a macro.
+    fn expand_placeholders(&mut self, item: &TokenStream) -> darling::Result<()> {
+        if let Some(content) = self
+            .content
+            .as_ref()
+            .filter(|_| self.content_call.is_none())
+        {
+            let expanded = placeholder::expand(&content.value(), item)
+                .map_err(|err| darling::Error::custom(err).with_span(content))?;
+            // The user's literal is kept if it contains no placeholders.
+            if expanded != content.value() {
+                self.content = Some(LitStr::new(&expanded, content.span()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that an issue tracker is configured if the box refers to a tracking issue.
+    fn check_issue(&self, metadata: &Metadata) -> darling::Result<()> {
+        check_issue(metadata, self.issue.as_ref())
+    }
+
+    /// Returns the HTML for the box's content, preceded by its icon and followed by its tracking
+    /// issue.
+    fn content_html(&self, metadata: &Metadata) -> Spliced {
+        let content = match (&self.content_call, self.markdown(), self.raw_html()) {
+            (Some(call), ..) => Spliced::call(call.clone()),
+            (None, true, raw_html) => markdown::render_inline(&self.content(), raw_html).into(),
+            (None, false, true) => self.content().into(),
+            (None, false, false) => html::escape(&self.content()).into(),
+        };
+        Spliced::from(self.icon_html()) + content + self.issue_html(metadata)
+    }
+
+    /// Returns the content as Markdown, preceded by its icon and followed by its tracking issue, for
+    /// consumers other than rustdoc's HTML output.
+    fn content_markdown(&self, metadata: &Metadata) -> Spliced {
+        let content = self.content();
+        let content = match (&self.content_call, self.raw_html(), self.markdown()) {
+            (Some(call), ..) => Spliced::call(call.clone()),
+            (None, true, _) => content.into(),
+            (None, false, true) => markdown::escape_html(&content).unwrap_or(content).into(),
+            (None, false, false) => markdown::escape_text(&content).into(),
+        };
+        // Bundled icons are images, and are therefore omitted.
+        let content = match self.icon.as_ref().filter(|icon| !icon::is_name(icon)) {
+            Some(icon) if !content.is_empty() => {
+                Spliced::from(format!("{} ", markdown::escape_text(icon))) + content
+            }
+            _ => content,
+        };
+        match &self.issue {
+            Some(issue) => {
+                content + format!(" (see issue {})", issue_link_markdown(metadata, **issue))
+            }
+            None => content,
+        }
+    }
+
+    /// Returns the `style` attribute applying the box's colors, or nothing if it has none.
+    fn style(&self) -> String {
+        let declarations = [
+            ("background-color:", &self.background),
+            ("border:1px solid ", &self.border),
+            ("color:", &self.color),
+        ]
+        .iter()
+        .filter_map(|(property, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{property}{}", value.as_str()))
+        })
+        .collect::<Vec<_>>();
+        if declarations.is_empty() {
+            String::new()
+        } else {
+            format!(" style='{}'", declarations.join(";"))
+        }
+    }
+
+    fn validate(&self) -> darling::Result<()> {
+        let mut errors = darling::Error::accumulator();
+        if let Some(class) = self
+            .class
+            .split_ascii_whitespace()
+            .find(|class| !html::is_class_name(class))
+        {
+            errors.push(
+                darling::Error::custom(format!("`{class}` is not a valid CSS class name"))
+                    .with_span(&self.class),
+            );
+        }
+        for color in [&self.background, &self.border, &self.color]
+            .iter()
+            .filter_map(|color| color.as_ref())
+        {
+            if !css::is_color(color) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{}` is not a valid CSS color",
+                        color.as_str()
+                    ))
+                    .with_span(color),
+                );
+            }
+        }
+        if let Some(icon) = self
+            .icon
+            .as_ref()
+            .filter(|icon| icon::is_name(icon) && icon::svg(icon).is_none())
+        {
+            errors.push(
+                darling::Error::custom(format!(
+                    "unknown icon `{}`, expected an emoji or one of {}",
+                    icon.as_str(),
+                    icon::names()
+                        .map(|name| format!("`{name}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+                .with_span(icon),
+            );
+        }
+        if let (Some(content), true) = (
+            self.content
+                .as_ref()
+                .filter(|_| self.content_call.is_none()),
+            self.raw_html(),
+        ) {
+            let html = if self.markdown() {
+                markdown::html(&content.value())
+            } else {
+                content.value()
+            };
+            if let Err(err) = html::check(&html) {
+                errors.push(darling::Error::custom(err).with_span(content));
+            }
+        }
+        errors.finish()
+    }
 }
 
 #[derive(FromMeta)]
 struct SinceArgs {
     #[darling(default)]
-    content: String,
+    content: SpannedValue<String>,
+    #[darling(default)]
+    free_form: bool,
+    /// The macro call providing the `content`, if any, taken out by [`splice::extract`].
+    #[darling(skip)]
+    content_call: Option<TokenStream>,
+}
+
+impl SinceArgs {
+    /// Returns the content, with `f` applied to it unless it is provided by a macro.
+    fn content(&self, f: impl Fn(&str) -> String) -> Spliced {
+        self.content_call.as_ref().map_or_else(
+            || f(&self.content).into(),
+            |call| Spliced::call(call.clone()),
+        )
+    }
+
+    /// Expands the placeholders within the content, unless it is provided by a macro.
+    fn expand_placeholders(&mut self, item: &TokenStream) -> darling::Result<()> {
+        if self.content_call.is_none() {
+            let expanded = placeholder::expand(&self.content, item)
+                .map_err(|err| darling::Error::custom(err).with_span(&self.content))?;
+            self.content = SpannedValue::new(expanded, self.content.span());
+        }
+        Ok(())
+    }
+}
+
+#[derive(FromMeta)]
+struct StableArgs {
+    since: SpannedValue<String>,
+    #[darling(default)]
+    const_since: Option<SpannedValue<String>>,
+    #[darling(default)]
+    free_form: bool,
+}
+
+#[derive(FromMeta)]
+struct UnstableArgs {
+    feature: String,
+    #[darling(default)]
+    issue: Option<SpannedValue<u32>>,
+    #[darling(default)]
+    reason: Option<String>,
+}
+
+const SEMI_TRANSPARENT_HTML: &str = "<span data-doc-item='semi-transparent'></span>";
+
+/// Checks that an issue tracker is configured if a tracking issue is referred to.
+fn check_issue(metadata: &Metadata, issue: Option<&SpannedValue<u32>>) -> darling::Result<()> {
+    match issue {
+        Some(issue) if metadata.issue_url.is_none() => Err(darling::Error::custom(
+            "`issue` requires an issue tracker to be configured, such as `issue_url = \"https://github.com/org/repo/issues/{}\"` in the `[package.metadata.doc_item]` table of `Cargo.toml`",
+        )
+        .with_span(issue)),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the HTML referring to an issue, linking to it if an issue tracker is configured.
+fn issue_link_html(metadata: &Metadata, issue: u32) -> String {
+    metadata.issue_url(issue).map_or_else(
+        || format!("#{issue}"),
+        |url| format!("<a href='{}'>#{issue}</a>", html::escape(&url)),
+    )
+}
+
+/// Returns the Markdown referring to an issue, linking to it if an issue tracker is configured.
+fn issue_link_markdown(metadata: &Metadata, issue: u32) -> String {
+    metadata
+        .issue_url(issue)
+        .map_or_else(|| format!("#{issue}"), |url| format!("[#{issue}](<{url}>)"))
+}
+
+fn doc_attribute(doc: impl Into<Spliced>) -> TokenStream {
+    TokenStream::from_str(&format!("#[doc = {}]", doc.into().expression())).unwrap()
+}
+
+/// Returns an attribute including the file at the given path, so that the compiler tracks it and
+/// the documentation is regenerated whenever it changes.
+///
+/// The file is included as the value of an inert tool attribute, which affects neither the item nor
+/// its documentation.
+fn track_file(path: &str, span: Span) -> TokenStream {
+    set_span(
+        TokenStream::from_str(&format!(
+            "#[rustfmt::doc_item = ::core::include_str!({})]",
+            Literal::string(path)
+        ))
+        .unwrap(),
+        span,
+    )
+}
+
+/// Returns whether the documentation is being read as Markdown text, rather than rendered as HTML
+/// by rustdoc, as far as can be told.
+///
+/// Procedural macros are run within the process reading the documentation, so its arguments can be
+/// inspected: rustdoc generates its JSON output when given `--output-format json`, either directly
+/// or within an `@path` argument file, and rust-analyzer runs procedural macros in its own server
+/// to display IDE hovers. This detection is best-effort. Any other tool, or any other way of
+/// running these ones, is given the HTML.
+fn text_output() -> bool {
+    let mut args = env::args();
+    let is_rust_analyzer = args.next().map_or(false, |program| {
+        Path::new(&program)
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .map_or(false, |name| name.starts_with("rust-analyzer"))
+    });
+    // Arguments may be read from files, given as `@path`.
+    let mut args = args.flat_map(|arg| {
+        arg.strip_prefix('@').map_or_else(
+            || vec![arg.clone()],
+            |path| {
+                fs::read_to_string(path)
+                    .map(|contents| contents.lines().map(str::to_owned).collect())
+                    .unwrap_or_default()
+            },
+        )
+    });
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        json |= arg == "--output-format=json"
+            || (arg == "--output-format" && args.next().as_deref() == Some("json"));
+    }
+    is_rust_analyzer || json
+}
+
+/// Limits documentation attributes to rustdoc's HTML output.
+///
+/// The attributes are only left out if the documentation is known to be read as Markdown text, so
+/// that the HTML is never lost, such as for items documented from a dependency's compiled metadata.
+fn html_only(attributes: TokenStream) -> TokenStream {
+    if text_output() {
+        TokenStream::new()
+    } else {
+        attributes
+    }
+}
+
+/// Limits documentation attributes to tools displaying the documentation's Markdown text directly,
+/// such as IDE hovers and rustdoc's JSON output.
+fn text_only(attributes: TokenStream) -> TokenStream {
+    if text_output() {
+        attributes
+    } else {
+        TokenStream::new()
+    }
+}
+
+/// Returns a doc attribute adding the Markdown text as its own block, for consumers other than
+/// rustdoc's HTML output.
+fn text_attribute(text: impl Into<Spliced>, span: Span) -> TokenStream {
+    text_only(set_span(doc_attribute(Spliced::from("\n\n") + text), span))
+}
+
+/// Formats text as a Markdown block quote.
+///
+/// Only the lines of the text surrounding any macro calls are quoted.
+fn blockquote(text: impl Into<Spliced>) -> Spliced {
+    Spliced::from("> ") + text.into().map_text(|text| text.replace('\n', "\n> "))
+}
+
+/// Begins markup appended to the end of the item's documentation, as its own block.
+///
+/// rustdoc uses the first block of an item's documentation as its summary in module lists. The
+/// markup is preceded by an HTML comment on its own line, so that for an item without any other
+/// documentation, the summary is the comment rather than the markup.
+const APPENDED_BLOCK: &str = "\n <!-- doc_item -->\n ";
+
+/// Returns the `data-doc-item` attribute marking markup of the given kind for the bootstrap, or
+/// nothing if scripts are disabled, in which case the markup is left where it is.
+fn marker(kind: &str, no_js: bool) -> String {
+    if no_js {
+        String::new()
+    } else {
+        format!(" {}='{kind}'", layouts::MARKER)
+    }
+}
+
+fn docbox_attribute(
+    class: &str,
+    style: &str,
+    content: Spliced,
+    bootstrap: &str,
+    no_js: bool,
+) -> TokenStream {
+    doc_attribute(
+        Spliced::from(format!(
+            "{APPENDED_BLOCK}<div class='item-info'{}><div class='stab {class}'{style}>",
+            marker("docbox", no_js)
+        )) + content
+            + format!("</div></div>{bootstrap}"),
+    )
+}
+
+/// Returns the doc attributes for a docbox whose content is rendered by rustdoc as Markdown.
+///
+/// The content is separated from the HTML opening and closing the box by blank lines, causing
+/// rustdoc to render it as Markdown.
+fn markdown_docbox_attributes(
+    open: &str,
+    content: TokenStream,
+    close: &str,
+    bootstrap: &str,
+    no_js: bool,
+) -> TokenStream {
+    let mut result = doc_attribute(format!(
+        "{APPENDED_BLOCK}<div class='item-info'{}>{open}",
+        marker("docbox", no_js)
+    ));
+    result.extend(doc_attribute(""));
+    result.extend(content);
+    result.extend(doc_attribute(""));
+    result.extend(doc_attribute(format!("{close}</div>{bootstrap}")));
+    result
+}
+
+/// Returns the doc attributes for the Markdown content of a docbox, preceded by its icon and
+/// followed by its tracking issue.
+fn markdown_content_attributes(
+    box_args: &BoxArgs,
+    content: &LitStr,
+    metadata: &Metadata,
+) -> TokenStream {
+    // Intra-doc links within the content are resolved by rustdoc. The user's literal is used as-is
+    // unless HTML must be escaped.
+    let content_attribute = box_args.content_call.as_ref().map_or_else(
+        || {
+            let mut literal = content.token();
+            if !box_args.raw_html() {
+                if let Some(escaped) = markdown::escape_html(&content.value()) {
+                    literal = proc_macro2::Literal::string(&escaped);
+                    literal.set_span(content.span());
+                }
+            }
+            let mut content_doc = TokenStream::from_str("doc =").unwrap();
+            content_doc.extend(TokenStream::from(proc_macro2::TokenStream::from(
+                proc_macro2::TokenTree::from(literal),
+            )));
+            let mut content_attribute = TokenStream::from_str("#").unwrap();
+            content_attribute.extend(TokenStream::from(TokenTree::from(Group::new(
+                Delimiter::Bracket,
+                content_doc,
+            ))));
+            content_attribute
+        },
+        |call| doc_attribute(Spliced::call(call.clone())),
+    );
+    let mut result = TokenStream::new();
+    // The icon is on the line directly before the content, placing it within the same paragraph.
+    let icon = box_args.icon_html();
+    if !icon.is_empty() {
+        result.extend(doc_attribute(icon));
+    }
+    result.extend(content_attribute);
+    // The issue is on the line directly after the content, for the same reason.
+    let issue = box_args.issue_html(metadata);
+    if !issue.is_empty() {
+        result.extend(doc_attribute(issue));
+    }
+    result
+}
+
+/// Sets the span of every token in the stream.
+///
+/// Generated tokens are given spans within the user's source, rather than the macro's call site.
+/// This keeps rustdoc from treating the item's documentation as coming from a macro expansion,
+/// which would suppress its lints, and points diagnostics at the relevant source.
+fn set_span(stream: TokenStream, span: Span) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), set_span(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::from(respanned)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
+}
+
+/// Returns the span of the attribute's arguments, to be used for tokens generated from them.
+fn attribute_span(attr: &TokenStream) -> Span {
+    attr.clone()
+        .into_iter()
+        .next()
+        .map_or_else(Span::call_site, |token| token.span())
+}
+
+/// Returns the HTML for a short docbox.
+///
+/// The short docbox is marked even if scripts are disabled, in which case the stylesheet uses the
+/// marker to hide it on the item's own page.
+fn short_docbox_html(
+    class: &str,
+    style: &str,
+    title: Option<&str>,
+    content: impl Into<Spliced>,
+) -> Spliced {
+    let title = title.map_or_else(String::new, |title| format!(" title='{title}'"));
+    Spliced::from(format!(
+        "<span class='stab {class}'{style}{title}{}>",
+        marker("short-docbox", false)
+    )) + content
+        + "</span>"
+}
+
+fn load_metadata() -> darling::Result<Rc<Metadata>> {
+    Metadata::load().map_err(darling::Error::custom)
+}
+
+fn check_versions(metadata: &Metadata, versions: &[&SpannedValue<String>]) -> darling::Result<()> {
+    if !metadata.strict_since {
+        return Ok(());
+    }
+    let mut errors = darling::Error::accumulator();
+    for version in versions {
+        errors.handle(check_version(version));
+    }
+    errors.finish()
+}
+
+fn check_version(version: &SpannedValue<String>) -> darling::Result<()> {
+    let parsed = Version::parse(version).map_err(|err| {
+        darling::Error::custom(format!(
+            "`{}` is not a valid semantic version: {err}",
+            version.as_str()
+        ))
+        .with_span(version)
+    })?;
+    if let Some(crate_version) = env::var("CARGO_PKG_VERSION")
+        .ok()
+        .and_then(|crate_version| Version::parse(&crate_version).ok())
+    {
+        if parsed > crate_version {
+            return Err(darling::Error::custom(format!(
+                "version `{parsed}` is greater than the crate's version `{crate_version}`"
+            ))
+            .with_span(version));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the doc attribute displaying a since value.
+///
+/// The bootstrap moves the value to the right of the item's definition, matching rustdoc's own
+/// since values. If scripts are disabled, the value is displayed in its own box instead.
+fn since_attribute(since: Spliced, bootstrap: &str, no_js: bool) -> TokenStream {
+    let (open, close) = if no_js {
+        (
+            "<div class='item-info'><div class='stab'>Since ".to_owned(),
+            "</div></div>",
+        )
+    } else {
+        (format!("<div{}>", marker("since", no_js)), "</div>")
+    };
+    doc_attribute(Spliced::from(APPENDED_BLOCK) + open + since + format!("{close}{bootstrap}"))
+}
+
+/// Returns the text of a doc attribute, if it is provided as a string literal.
+fn doc_text(attribute: &Attribute) -> Option<String> {
+    if !is_doc_text(attribute) {
+        return None;
+    }
+    match attribute.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(text),
+            ..
+        })) => Some(text.value()),
+        _ => None,
+    }
+}
+
+/// Returns the attributes at the start of the item.
+fn attributes(item: &TokenStream) -> Vec<Attribute> {
+    let mut attributes = Vec::new();
+    let mut item_iter = item.clone().into_iter();
+    while let (Some(TokenTree::Punct(punct)), Some(group)) = (item_iter.next(), item_iter.next()) {
+        if punct.as_char() != '#' {
+            break;
+        }
+        let mut attribute = TokenStream::from(TokenTree::from(punct));
+        attribute.extend(TokenStream::from(group));
+        attributes.extend(Attribute::parse_outer.parse(attribute).unwrap_or_default());
+    }
+    attributes
+}
+
+/// Returns the item, along with the HTML of the assets to include with markup added to the end of
+/// its documentation.
+///
+/// Assets that the documentation already includes are omitted, except for the bootstrap, which
+/// must follow all of the markup it places. Its copies within markup appended previously are
+/// removed from the item instead.
+fn appended_assets(item: TokenStream, assets: Vec<String>) -> (TokenStream, String) {
+    let docs = attributes(&item)
+        .iter()
+        .filter_map(doc_text)
+        .collect::<Vec<_>>();
+    let (bootstraps, styles): (Vec<_>, Vec<_>) = assets
+        .into_iter()
+        .partition(|asset| bootstrap::is_bootstrap(asset));
+    let included = styles
+        .into_iter()
+        .filter(|asset| !docs.iter().any(|text| text.contains(asset)))
+        .chain(bootstraps.iter().cloned())
+        .collect();
+
+    let mut result = TokenStream::new();
+    let mut item_iter = item.into_iter();
+    while let Some(token) = item_iter.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                let span = punct.span();
+                let mut attribute = TokenStream::from(TokenTree::from(punct));
+                attribute.extend(item_iter.next().map(TokenStream::from));
+                let moved = Attribute::parse_outer
+                    .parse(attribute.clone())
+                    .ok()
+                    .and_then(|attributes| attributes.iter().find_map(doc_text))
+                    .and_then(|text| {
+                        let moved = bootstraps
+                            .iter()
+                            .fold(text.clone(), |text, asset| text.replace(asset.as_str(), ""));
+                        Some(moved).filter(|moved| *moved != text)
+                    });
+                match moved {
+                    Some(text) => result.extend(set_span(doc_attribute(text), span)),
+                    None => result.extend(attribute),
+                }
+            }
+            token => {
+                result.extend(TokenStream::from(token));
+                result.extend(item_iter);
+                break;
+            }
+        }
+    }
+    (result, included)
 }
 
 fn insert_after_attributes(
@@ -107,7 +1044,7 @@ fn insert_after_attributes(
         } else {
             result.extend(value);
             Extend::extend::<TokenStream>(result, token.into());
-            Extend::extend::<TokenStream>(result, item_iter.collect());
+            result.extend(item_iter);
             return;
         }
     }
@@ -115,37 +1052,103 @@ fn insert_after_attributes(
     result.extend(value);
 }
 
-fn prepend_to_doc(result: &mut TokenStream, value: &str, item_iter: &mut token_stream::IntoIter) {
+/// Returns whether the attribute is documentation text, such as `#[doc = "..."]` or
+/// `#[doc = include_str!("...")]`, as opposed to an attribute like `#[doc(hidden)]`.
+fn is_doc_text(attribute: &Attribute) -> bool {
+    attribute.path.is_ident("doc")
+        && matches!(
+            attribute.tokens.clone().into_iter().next(),
+            Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == '='
+        )
+}
+
+/// Inserts `value` as documentation preceding the item's existing documentation.
+///
+/// The value is inserted as its own doc attribute directly before the first attribute containing
+/// documentation text, leaving the existing attributes exactly as they were written. The value must
+/// begin with an inline element, so that rustdoc renders it within the same paragraph as the first
+/// line of the existing documentation, keeping both in module summaries. The value is escaped so
+/// that it is not altered by rustdoc's Markdown processing. The inserted attribute is given the
+/// span of the token it precedes, and is limited to rustdoc's HTML output.
+///
+/// The paragraph includes the assets, unless it already does. Any copies included with markup
+/// appended previously are removed, as the paragraph is displayed along with that markup.
+fn prepend_to_doc(
+    result: &mut TokenStream,
+    value: impl Into<Spliced>,
+    item_iter: &mut token_stream::IntoIter,
+    assets: &[String],
+) {
+    let value = value.into();
+    let mut attributes = Vec::new();
+    let mut next = None;
     while let Some(token) = item_iter.next() {
-        if token.to_string() == "#" {
-            let attribute = item_iter.next().unwrap().to_string();
-            if attribute.starts_with("[doc =") {
-                Extend::extend::<TokenStream>(result, token.into());
-                let mut old_doc = attribute
-                    .splitn(2, '\"')
-                    .skip(1)
-                    .collect::<String>()
-                    .trim_start()
-                    .trim_end_matches("\"]")
-                    .to_owned();
-                if !old_doc.starts_with('<') {
-                    old_doc = format!("<p>{}</p>", old_doc);
-                }
-                Extend::extend::<TokenStream>(
-                    result,
-                    TokenStream::from_str(&format!("[doc = \"{}{}\"]", value, old_doc)).unwrap(),
-                );
-                return;
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                let mut attribute = TokenStream::from(TokenTree::from(punct));
+                attribute.extend(TokenStream::from(item_iter.next().unwrap()));
+                let doc = Attribute::parse_outer
+                    .parse(attribute.clone())
+                    .ok()
+                    .and_then(|attributes| attributes.into_iter().find(is_doc_text));
+                attributes.push((attribute, doc));
+            }
+            token => {
+                next = Some(token);
+                break;
             }
-            Extend::extend::<TokenStream>(result, token.into());
-            Extend::extend::<TokenStream>(result, TokenStream::from_str(&attribute).unwrap());
-        } else {
-            // There are no more attributes, and therefore no more docs.
-            result.extend(TokenStream::from_str(&format!("#[doc = \"{}\"]", value)).unwrap());
-            Extend::extend::<TokenStream>(result, token.into());
-            return;
         }
     }
+
+    let texts = attributes
+        .iter()
+        .map(|(_, doc)| doc.as_ref().and_then(doc_text))
+        .collect::<Vec<_>>();
+    // Markup prepended previously is in the same paragraph, and its assets are shared.
+    let prepended_assets = assets
+        .iter()
+        .filter(|asset| {
+            !texts
+                .iter()
+                .flatten()
+                .any(|text| !text.starts_with(APPENDED_BLOCK) && text.contains(asset.as_str()))
+        })
+        .map(String::as_str)
+        .collect::<String>();
+    let prepended_attribute = |span| {
+        html_only(set_span(
+            doc_attribute(value.map_text(html::escape_markdown) + prepended_assets.as_str()),
+            span,
+        ))
+    };
+
+    let mut inserted = false;
+    for ((attribute, doc), text) in attributes.into_iter().zip(texts) {
+        let span = attribute.clone().into_iter().next().unwrap().span();
+        if doc.is_some() && !inserted {
+            result.extend(prepended_attribute(span));
+            inserted = true;
+        }
+        let moved = text
+            .filter(|text| text.starts_with(APPENDED_BLOCK))
+            .and_then(|text| {
+                let moved = assets
+                    .iter()
+                    .fold(text.clone(), |text, asset| text.replace(asset.as_str(), ""));
+                Some(moved).filter(|moved| *moved != text)
+            });
+        match moved {
+            Some(text) => result.extend(html_only(set_span(doc_attribute(text), span))),
+            None => result.extend(attribute),
+        }
+    }
+    if let Some(token) = next {
+        if !inserted {
+            // There are no docs.
+            result.extend(prepended_attribute(token.span()));
+        }
+        result.extend(TokenStream::from(token));
+    }
 }
 
 /// Adds a docbox to the item's item-info.
@@ -163,9 +1166,10 @@ fn prepend_to_doc(result: &mut TokenStream, value: &str, item_iter: &mut token_s
 ///
 /// The docbox can be styled using the `class` parameter. The class corresponds to a CSS class in
 /// the generated HTML. In the above example, `"unstable"` was used, as it is already a predefined
-/// class by rustdoc. Other predefined classes include `"portability"` and `"deprecated"`. If
-/// different style is desired, a custom class can be provided using the `--html-in-header` rustdoc
-/// flag.
+/// class by rustdoc. Other predefined classes include `"portability"` and `"deprecated"`, along
+/// with the classes provided by this crate: `"info"`, `"note"`, `"warning"`, `"danger"`,
+/// `"success"`, and `"security"`. If different style is desired, a custom class can be defined
+/// using the [`styles!`] macro.
 ///
 /// Provide a custom class like this:
 ///
@@ -174,22 +1178,181 @@ fn prepend_to_doc(result: &mut TokenStream, value: &str, item_iter: &mut token_s
 /// pub fn foo() {}
 /// ```
 ///
-/// Define the custom class in a separate file, potentially named `custom.html`.
+/// Define the custom class in the crate root.
+///
+/// ```
+/// doc_item::styles!(".custom { background: #f5ffd6; border-color: #b9ff00; }");
+/// # fn main() {}
+/// ```
+///
+/// For a one-off box, colors can instead be provided directly using the `background`, `border`,
+/// and `color` parameters, which are applied on top of the `class`. Each must be a CSS color, such
+/// as `"#c4ffd7"`, `"teal"`, `"rgb(196 255 215)"`, or `"var(--doc-item-success-border)"`.
+///
+/// ```
+/// #[doc_item::docbox(content="A one-off docbox", class="note", background="#c4ffd7", border="teal")]
+/// pub fn foo() {}
+/// ```
+///
+/// # Icons
+///
+/// An `icon` can be displayed before the content, like the 🔬 displayed by the standard library's
+/// experimental APIs. The icon may be an emoji, or the name of one of the bundled icons:
+/// `"experimental"`, `"warning"`, `"lock"`, `"platform"`, `"feature"`, or `"deprecated"`. Bundled
+/// icons are drawn in the box's text color, and are sized to match rustdoc's own icons.
+///
+/// ```
+/// #[doc_item::docbox(content="This API is experimental", class="unstable", icon="experimental")]
+/// pub fn foo() {}
+/// ```
+///
+/// # Markdown
+///
+/// The `content` is rendered as Markdown, allowing for code spans, emphasis, and links:
+///
+/// ```
+/// #[doc_item::docbox(content="Use `bar` *instead*. See [the guide](https://example.com).", class="unstable")]
+/// pub fn foo() {}
+/// ```
+///
+/// If the `content` should be used as plain text without any Markdown processing, provide
+/// `markdown = false`:
 ///
-/// ```html
-/// <style>
-///     .custom {
-///         background: #f5ffd6;
-///         border-color: #b9ff00;
-///     }
-/// </style>
 /// ```
+/// #[doc_item::docbox(content="Not *emphasized*", class="unstable", markdown=false)]
+/// pub fn foo() {}
+/// ```
+///
+/// ## Intra-doc Links
 ///
-/// And finally build the documentation with the custom docbox class.
+/// Since the `content` is rendered by rustdoc along with the rest of the item's documentation,
+/// [intra-doc links](https://doc.rust-lang.org/rustdoc/write-documentation/linking-to-items-by-name.html)
+/// are resolved just as they are in doc comments:
 ///
-/// ```bash
-/// $ RUSTDOCFLAGS="--html-in-header custom.html" cargo doc --no-deps --open
 /// ```
+/// #[doc_item::docbox(content="Use [`bar`] instead.", class="unstable")]
+/// pub fn foo() {}
+///
+/// pub fn bar() {}
+/// ```
+///
+/// Unresolved links are reported by the `rustdoc::broken_intra_doc_links` lint, pointing at the
+/// attribute.
+///
+/// # HTML
+///
+/// Any HTML within the `content` is escaped, and is therefore displayed as text. To use HTML within
+/// the `content`, provide the `raw_html` parameter:
+///
+/// ```
+/// #[doc_item::docbox(content="<span class='emoji'>🔬</span> Experimental", class="unstable", raw_html)]
+/// pub fn foo() {}
+/// ```
+///
+/// When combined with `markdown = false`, the `content` is used as raw HTML without any Markdown
+/// processing.
+///
+/// Raw HTML is checked for well-formedness during compilation. Every tag must be closed in the
+/// order it was opened, void elements such as `<br>` must not be closed, and other elements must
+/// not be self-closing. `<script>` tags are not allowed, as they would interfere with the scripts
+/// `doc_item` uses to place the docbox.
+///
+/// The `class` must be one or more valid CSS class names separated by whitespace. Otherwise,
+/// compilation will fail.
+///
+/// # Tracking Issues
+///
+/// A docbox can refer to an issue in the crate's issue tracker using the `issue` parameter. The
+/// issue is displayed after the content as "see issue #42", linking to the issue.
+///
+/// ```ignore
+/// #[doc_item::docbox(content="This API is experimental", class="unstable", issue=42)]
+/// pub fn foo() {}
+/// ```
+///
+/// The issue's URL is built from the `issue_url` template in the `[package.metadata.doc_item]`
+/// table, with `{}` in place of the issue number. Compilation fails if an `issue` is provided
+/// without a template.
+///
+/// ```toml
+/// [package.metadata.doc_item]
+/// issue_url = "https://github.com/org/repo/issues/{}"
+/// ```
+///
+/// # Content Files
+///
+/// Longer content, such as a notice shared by many items, can be kept in its own Markdown file and
+/// provided using the `content_file` parameter instead of `content`. The path is relative to the
+/// directory containing the crate's `Cargo.toml`.
+///
+/// ```ignore
+/// #[doc_item::docbox(content_file="docs/notices/fips.md", class="security")]
+/// pub fn foo() {}
+/// ```
+///
+/// The file's contents are treated exactly as a `content` literal would be: they are rendered as
+/// Markdown unless `markdown = false` is provided, HTML within them is escaped unless `raw_html`
+/// is provided, and [placeholders](#placeholders) are expanded. Compilation fails if the file
+/// can't be read, and the file is tracked by the compiler, so changes to it are picked up by the
+/// next build.
+///
+/// # Presets
+///
+/// A `preset` refers to a set of parameters defined in the `[package.metadata.doc_item.presets]`
+/// table of the crate's `Cargo.toml`. A preset may define any of the docbox's parameters other
+/// than `preset` itself, including `content_file`, along with the `short` content used by short
+/// docboxes.
+///
+/// ```toml
+/// [package.metadata.doc_item.presets.experimental]
+/// content = "This is an experimental API."
+/// short = "Experimental"
+/// class = "unstable"
+/// ```
+///
+/// Parameters provided by the attribute take precedence over those of the preset:
+///
+/// ```ignore
+/// #[doc_item::docbox(preset="experimental", class="warning")]
+/// pub fn foo() {}
+/// ```
+///
+/// Compilation fails if the preset is not defined. Values taken from the preset are validated
+/// the same way as the attribute's own, and errors in them are reported at the `preset` parameter.
+/// [Placeholders](#placeholders) within the preset's content are expanded for each item using it.
+///
+/// # Placeholders
+///
+/// The `content` may contain the following placeholders, which are replaced when the attribute is
+/// evaluated:
+///
+/// - `{crate}`: the name of the crate's package.
+/// - `{version}`: the version of the crate's package.
+/// - `{item}`: the name of the annotated item.
+/// - `{kind}`: the kind of the annotated item, such as `function`, `struct`, or `trait`.
+///
+/// ```
+/// #[doc_item::docbox(content="Added to {crate} in {version}; see {kind} `{item}`.", class="info")]
+/// pub fn foo() {}
+/// ```
+///
+/// Literal braces are written by doubling them, as `{{` and `}}`. Compilation fails if the
+/// `content` contains any other placeholder, or an unmatched brace. `{item}` and `{kind}` are not
+/// available on items without a name, such as `impl` blocks.
+///
+/// # Macro Content
+///
+/// The `content` may also be provided by a macro such as `concat!` or `env!`, which is evaluated
+/// by the compiler:
+///
+/// ```
+/// #[doc_item::docbox(content=concat!("Added in ", env!("CARGO_PKG_VERSION")), class="unstable")]
+/// pub fn foo() {}
+/// ```
+///
+/// As its value is not known until the macro is evaluated, it is not escaped, and placeholders
+/// within it are not expanded. The `class` must always be a string literal, so that it can be
+/// validated.
 ///
 /// # Multiple Docboxes
 /// Multiple docbox attributes may be used on a single item. When generating the documentation,
@@ -205,27 +1368,86 @@ fn prepend_to_doc(result: &mut TokenStream, value: &str, item_iter: &mut token_s
 /// will result in the `"portability"` docbox being displayed above the `"unstable"` docbox.
 #[proc_macro_attribute]
 pub fn docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let box_args = match BoxArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
+    let span = attribute_span(&attr);
+    let (attr, content_call) = splice::extract(attr);
+    let mut box_args = match BoxArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
         Ok(args) => args,
         Err(err) => {
             return err.write_errors().into();
         }
     };
+    box_args.content_call = content_call;
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
 
-    let mut result = TokenStream::new();
+    if let Err(err) = box_args.apply_preset(&metadata, false) {
+        return err.write_errors().into();
+    }
 
-    // Insert the box after all other attributes.
-    insert_after_attributes(
-        &mut result,
-        TokenStream::from_str(&format!(
-            "#[doc = \"\n <div class='item-info'><div class='stab {}'>{}</div></div><script>var box = document.currentScript.previousElementSibling;if(box.parentElement.classList.contains('docblock-short')){{box.remove();}}else if(box.parentElement.parentElement.classList.contains('top-doc')){{box.parentElement.parentElement.before(box);}}else{{box.parentElement.before(box);}}document.currentScript.remove();</script>\"]",
-            box_args.class,
-            box_args.content
-        ))
-        .unwrap(),
-        item.into_iter()
+    if let Err(err) = box_args.read_content_file() {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.expand_placeholders(&item) {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.validate() {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.check_issue(&metadata) {
+        return err.write_errors().into();
+    }
+
+    let mut result = TokenStream::new();
+    let (item, bootstrap) = appended_assets(
+        item,
+        bootstrap::assets(&metadata, &box_args.class, &box_args.style(), false),
     );
 
+    // Insert the box after all other attributes. Every token is given the span of the content, if
+    // it is rendered as Markdown, so that any warnings about the content point back to it.
+    let mut docbox = html_only(match (&box_args.content, box_args.markdown()) {
+        (Some(content), true) => set_span(
+            markdown_docbox_attributes(
+                &format!(
+                    "<div class='stab {}'{}>",
+                    box_args.class.as_str(),
+                    box_args.style()
+                ),
+                markdown_content_attributes(&box_args, content, &metadata),
+                "</div>",
+                &bootstrap,
+                metadata.no_js,
+            ),
+            content.span().unwrap(),
+        ),
+        _ => set_span(
+            docbox_attribute(
+                &box_args.class,
+                &box_args.style(),
+                box_args.content_html(&metadata),
+                &bootstrap,
+                metadata.no_js,
+            ),
+            span,
+        ),
+    });
+    let content = box_args.content_markdown(&metadata);
+    if !content.is_empty() {
+        docbox.extend(text_attribute(blockquote(content), span));
+    }
+    if let Some(path) = &box_args.content_path {
+        docbox.extend(track_file(path, span));
+    }
+    insert_after_attributes(&mut result, docbox, item.into_iter());
+
     result
 }
 
@@ -249,8 +1471,9 @@ pub fn docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// The short docbox can be styled using the `class` parameter. The class corresponds to a CSS class
 /// in the generated HTML. In the above example, `"unstable"` was used, as it is already a
 /// predefined class by rustdoc. Other predefined classes include `"portability"` and
-/// `"deprecated"`. If different style is desired, a custom class can be provided using the
-/// `--html-in-header` rustdoc flag.
+/// `"deprecated"`, along with the classes provided by this crate: `"info"`, `"note"`,
+/// `"warning"`, `"danger"`, `"success"`, and `"security"`. If different style is desired, a custom
+/// class can be defined using the [`styles!`] macro.
 ///
 /// Provide a custom class like this:
 ///
@@ -259,21 +1482,43 @@ pub fn docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// pub fn foo() {}
 /// ```
 ///
-/// Define the custom class in a separate file, potentially named `custom.html`.
+/// Define the custom class in the crate root.
+///
+/// ```
+/// doc_item::styles!(".custom { background: #f5ffd6; border-color: #b9ff00; }");
+/// # fn main() {}
+/// ```
+///
+/// As with [`macro@docbox`], the `background`, `border`, `color`, and `icon` parameters can be used to
+/// style a one-off short docbox, and the `icon` may be an emoji or the name of a bundled icon.
+///
+/// ```
+/// #[doc_item::short_docbox(content="Locked", background="#c4ffd7", icon="lock")]
+/// pub fn foo() {}
+/// ```
+///
+/// # Markdown
+///
+/// As with [`macro@docbox`], the `content` is rendered as Markdown. Provide `markdown = false` to use
+/// the `content` as plain text instead. HTML within the `content` is escaped unless the `raw_html`
+/// parameter is provided, in which case it is checked for well-formedness. The `class` must consist
+/// of valid CSS class names.
 ///
-/// ```html
-/// <style>
-///     .custom {
-///         background: #f5ffd6;
-///         border-color: #b9ff00;
-///     }
-/// </style>
+/// ```
+/// #[doc_item::short_docbox(content="*Experimental*", class="unstable")]
+/// pub fn foo() {}
 /// ```
 ///
-/// And finally build the documentation with the custom docbox class.
+/// As with [`macro@docbox`], the `content` may also be provided by a macro such as `concat!` or
+/// `env!`, may be read from a `content_file`, and may contain placeholders such as `{version}`, and
+/// an `issue` may be referred to using the crate's `issue_url` template.
 ///
-/// ```bash
-/// $ RUSTDOCFLAGS="--html-in-header custom.html" cargo doc --no-deps --open
+/// A `preset` defined in the crate's `Cargo.toml` may also be used, in which case its `short`
+/// text is used as the content, falling back to its `content` or `content_file`:
+///
+/// ```ignore
+/// #[doc_item::short_docbox(preset="experimental")]
+/// pub fn foo() {}
 /// ```
 ///
 /// # Multiple Short Docboxes
@@ -289,26 +1534,76 @@ pub fn docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// will result in the `"portability"` short docbox being displayed to the left of the `"unstable"`
 /// short docbox.
+///
+/// # Macro-Valued Documentation
+/// The short docbox is inserted as its own doc attribute, so documentation provided by macros, such
+/// as `#[doc = include_str!("...")]`, is left as written and still appears after the short docbox
+/// in module lists.
+///
+/// ```
+/// #[doc_item::short_docbox(content="Experimental", class="unstable")]
+/// #[doc = concat!("Documentation ", "from a macro.")]
+/// pub fn foo() {}
+/// ```
 #[proc_macro_attribute]
 pub fn short_docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let box_args = match BoxArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
+    let span = attribute_span(&attr);
+    let (attr, content_call) = splice::extract(attr);
+    let mut box_args = match BoxArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
         Ok(args) => args,
         Err(err) => {
             return err.write_errors().into();
         }
     };
+    box_args.content_call = content_call;
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    if let Err(err) = box_args.apply_preset(&metadata, true) {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.read_content_file() {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.expand_placeholders(&item) {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.validate() {
+        return err.write_errors().into();
+    }
+
+    if let Err(err) = box_args.check_issue(&metadata) {
+        return err.write_errors().into();
+    }
 
     let mut result = TokenStream::new();
-    let mut item_iter = item.clone().into_iter();
+    if let Some(path) = &box_args.content_path {
+        result.extend(track_file(path, span));
+    }
+    let mut item_iter = item.into_iter();
 
     // Insert the short box.
-    let short_docbox = &format!(
-        "<script>document.currentScript.remove();</script><span class='stab {}'>{}</span><script>var box = document.currentScript.previousElementSibling;var classes = document.currentScript.parentElement.parentElement.getElementsByClassName('module-item');if (classes.length == 0) {{box.remove();}} else {{classes[0].append(box);}}document.currentScript.remove();</script>",
-        box_args.class, box_args.content
+    prepend_to_doc(
+        &mut result,
+        short_docbox_html(
+            &box_args.class,
+            &box_args.style(),
+            None,
+            box_args.content_html(&metadata),
+        ),
+        &mut item_iter,
+        &bootstrap::assets(&metadata, &box_args.class, &box_args.style(), true),
     );
-    prepend_to_doc(&mut result, short_docbox, &mut item_iter);
-        
-    Extend::extend::<TokenStream>(&mut result, item_iter.collect());
+
+    result.extend(item_iter);
 
     result
 }
@@ -326,17 +1621,30 @@ pub fn short_docbox(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_attribute]
 pub fn semi_transparent(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    // Module list rows can't be styled without a script.
+    if metadata.no_js {
+        return item;
+    }
+
     let mut result = TokenStream::new();
     let mut item_iter = item.into_iter();
 
-    // Insert script to gray the text.
+    // Insert the marker to gray the text.
     prepend_to_doc(
         &mut result,
-        "<script>var module_items = document.currentScript.parentElement.parentElement.getElementsByClassName('module-item'); if(module_items.length != 0){{module_items[0].classList.add('unstable');}}document.currentScript.remove();</script>",
-        &mut item_iter
+        SEMI_TRANSPARENT_HTML,
+        &mut item_iter,
+        &bootstrap::assets(&metadata, "", "", true),
     );
 
-    Extend::extend::<TokenStream>(&mut result, item_iter.collect());
+    result.extend(item_iter);
 
     result
 }
@@ -352,26 +1660,478 @@ pub fn semi_transparent(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// #[doc_item::since(content="1.2.0")]
 /// pub fn foo() {}
 /// ```
+///
+/// # Strict Versions
+///
+/// By default, the `content` may be any text. Strict version checking can be enabled for a crate by
+/// adding the following to its `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.doc_item]
+/// strict_since = true
+/// ```
+///
+/// In strict mode, the `content` must be a valid [semantic version](https://semver.org/) that is no
+/// greater than the crate's own version. Otherwise, compilation will fail. This catches typos and
+/// versions that have not been released yet.
+///
+/// Free-form text can still be used in strict mode by explicitly opting out with the `free_form`
+/// parameter:
+///
+/// ```
+/// #[doc_item::since(content="the beginning", free_form)]
+/// pub fn foo() {}
+/// ```
+///
+/// # Placeholders
+///
+/// As with [`macro@docbox`], placeholders within the `content` are expanded. `{version}` is
+/// replaced by the crate's version, which is useful for items added in the upcoming release:
+///
+/// ```
+/// #[doc_item::since(content="{version}")]
+/// pub fn foo() {}
+/// ```
+///
+/// Placeholders are expanded before the `content` is checked in strict mode.
+///
+/// # Macro Content
+///
+/// The `content` may also be provided by a macro such as `env!`. Such content is not escaped, and
+/// is not checked in strict mode.
+///
+/// ```
+/// #[doc_item::since(content=env!("CARGO_PKG_VERSION"))]
+/// pub fn foo() {}
+/// ```
 #[proc_macro_attribute]
 pub fn since(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let since_args = match SinceArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
+    let (attr, content_call) = splice::extract(attr);
+    let mut since_args = match SinceArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
         Ok(args) => args,
         Err(err) => {
             return err.write_errors().into();
         }
     };
+    since_args.content_call = content_call;
+
+    if let Err(err) = since_args.expand_placeholders(&item) {
+        return err.write_errors().into();
+    }
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    if !since_args.free_form && since_args.content_call.is_none() {
+        if let Err(err) = check_versions(&metadata, &[&since_args.content]) {
+            return err.write_errors().into();
+        }
+    }
 
     let mut result = TokenStream::new();
+    let (item, bootstrap) = appended_assets(item, bootstrap::assets(&metadata, "", "", false));
 
-    insert_after_attributes(
-        &mut result,
-        TokenStream::from_str(&format!(
-            "#[doc = \" <script>document.currentScript.remove();</script><span class='since'>{}</span><script>var since=document.currentScript.previousElementSibling;if(since.parentElement.classList.contains('docblock-short')){{since.remove();}}else if(since.parentElement.parentElement.classList.contains('top-doc')){{var out_of_band = since.parentElement.parentElement.parentElement.getElementsByClassName('out-of-band')[0];out_of_band.prepend(' · ');out_of_band.prepend(since);}}else{{var rightside = since.parentElement.parentElement.getElementsByClassName('rightside')[0];rightside.prepend(' · ');rightside.prepend(since);}}document.currentScript.remove();</script>\"]",
-            since_args.content
+    let span = since_args.content.span().unwrap();
+    let mut since = html_only(set_span(
+        since_attribute(
+            Spliced::from("<span class='since'>") + since_args.content(html::escape) + "</span>",
+            &bootstrap,
+            metadata.no_js,
+        ),
+        span,
+    ));
+    since.extend(text_attribute(
+        Spliced::from("*Since ") + since_args.content(markdown::escape_text) + "*",
+        span,
+    ));
+    insert_after_attributes(&mut result, since, item.into_iter());
+
+    result
+}
+
+/// Marks an item as stable since a certain version.
+///
+/// This mirrors the standard library's
+/// [`#[stable]`](https://rustc-dev-guide.rust-lang.org/stability.html#stable) attribute. The version
+/// is placed to the right of the item's definition in light text, the same as with
+/// [`macro@since`].
+///
+/// ```
+/// #[doc_item::stable(since = "1.2.0")]
+/// pub fn foo() {}
+/// ```
+///
+/// Note that this attribute shares its name with a built-in attribute, and therefore must be
+/// referred to by its full path, `doc_item::stable`.
+///
+/// # Const Stability
+///
+/// A `const fn` may become usable in const contexts in a later version than the one in which it
+/// was added. The version in which it became const can be provided using the `const_since`
+/// parameter:
+///
+/// ```
+/// #[doc_item::stable(since = "1.2.0", const_since = "1.4.0")]
+/// pub const fn foo() {}
+/// ```
+///
+/// Both versions are then displayed together, as `const: 1.4.0 · 1.2.0`.
+///
+/// # Strict Versions
+///
+/// When strict version checking is enabled, both `since` and `const_since` are checked in the same
+/// way as the `content` of the [`macro@since`] attribute, and the `free_form` parameter can
+/// likewise be used to opt out.
+#[proc_macro_attribute]
+pub fn stable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let stable_args = match StableArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
+        Ok(args) => args,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    if !stable_args.free_form {
+        let mut versions = vec![&stable_args.since];
+        versions.extend(&stable_args.const_since);
+        if let Err(err) = check_versions(&metadata, &versions) {
+            return err.write_errors().into();
+        }
+    }
+
+    let span = stable_args.since.span().unwrap();
+    let text = stable_args.const_since.as_ref().map_or_else(
+        || format!("*Since {}*", markdown::escape_text(&stable_args.since)),
+        |const_since| {
+            format!(
+                "*Since {}, const since {}*",
+                markdown::escape_text(&stable_args.since),
+                markdown::escape_text(const_since)
+            )
+        },
+    );
+    let since = html::escape(&stable_args.since);
+    let since = stable_args.const_since.map_or_else(
+        || format!("<span class='since' title='Stable since version {since}'>{since}</span>"),
+        |const_since| {
+            format!(
+                "<span class='since' title='Stable since version {since}, const since {0}'>const: {0} · {since}</span>",
+                html::escape(&const_since)
+            )
+        },
+    );
+
+    let mut result = TokenStream::new();
+    let (item, bootstrap) = appended_assets(item, bootstrap::assets(&metadata, "", "", false));
+
+    let mut since = html_only(set_span(
+        since_attribute(since.into(), &bootstrap, metadata.no_js),
+        span,
+    ));
+    since.extend(text_attribute(text, span));
+    insert_after_attributes(&mut result, since, item.into_iter());
+
+    result
+}
+
+/// Marks an item as experimental.
+///
+/// This mirrors the standard library's
+/// [`#[unstable]`](https://rustc-dev-guide.rust-lang.org/stability.html#unstable) attribute. The
+/// item is given an `"unstable"` docbox naming the feature it belongs to, an `"Experimental"` short
+/// docbox in module lists, and is displayed as semi-transparent in module lists.
+///
+/// ```
+/// #[doc_item::unstable(feature = "foo")]
+/// pub fn foo() {}
+/// ```
+///
+/// This is equivalent to using the [`macro@docbox`], [`macro@short_docbox`], and
+/// [`macro@semi_transparent`] attributes together.
+///
+/// Note that this attribute shares its name with a built-in attribute, and therefore must be
+/// referred to by its full path, `doc_item::unstable`.
+///
+/// # Tracking Issues
+///
+/// A tracking issue number can optionally be provided using the `issue` parameter. It is displayed
+/// alongside the feature name, linking to the issue using the crate's `issue_url` template, as
+/// described for [`macro@docbox`]. Compilation fails if an `issue` is provided without a template.
+///
+/// ```ignore
+/// #[doc_item::unstable(feature = "foo", issue = 42)]
+/// pub fn foo() {}
+/// ```
+///
+/// # Reasons
+///
+/// A reason for the item's instability can optionally be provided using the `reason` parameter.
+/// The reason is displayed in an expandable section within the docbox.
+///
+/// ```
+/// #[doc_item::unstable(feature = "foo", reason = "The API is still being designed.")]
+/// pub fn foo() {}
+/// ```
+#[proc_macro_attribute]
+pub fn unstable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let span = attribute_span(&attr);
+    let unstable_args = match UnstableArgs::from_list(&parse_macro_input!(attr as AttributeArgs)) {
+        Ok(args) => args,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    if let Err(err) = check_issue(&metadata, unstable_args.issue.as_ref()) {
+        return err.write_errors().into();
+    }
+
+    let issue = unstable_args.issue.map(|issue| *issue);
+    let mut text = format!(
+        "**Experimental** – This is an experimental API. ({}{})",
+        markdown::code_span(&unstable_args.feature),
+        issue.map_or_else(String::new, |issue| format!(
+            " {}",
+            issue_link_markdown(&metadata, issue)
         ))
-        .unwrap(),
-        item.into_iter()
     );
+    if let Some(reason) = &unstable_args.reason {
+        text = format!("{text}\n\n{}", markdown::escape_text(reason));
+    }
+
+    let issue = issue.map_or_else(String::new, |issue| {
+        format!("&nbsp;{}", issue_link_markdown(&metadata, issue))
+    });
+    let content = format!(
+        "<span class='emoji'>🔬</span> This is an experimental API. ({}{issue})",
+        markdown::code_span(&unstable_args.feature)
+    );
+    // The reason is collapsed beneath the rest of the content.
+    let (open, close) = unstable_args.reason.map_or_else(
+        || ("<div class='stab unstable'>", "</div>".to_owned()),
+        |reason| {
+            (
+                "<div class='stab unstable'><details><summary>",
+                format!("</summary><p>{}</p></details></div>", html::escape(&reason)),
+            )
+        },
+    );
+
+    let mut docboxed = TokenStream::new();
+    let (item, bootstrap) =
+        appended_assets(item, bootstrap::assets(&metadata, "unstable", "", false));
+    let mut docbox = html_only(set_span(
+        markdown_docbox_attributes(
+            open,
+            doc_attribute(content),
+            &close,
+            &bootstrap,
+            metadata.no_js,
+        ),
+        span,
+    ));
+    docbox.extend(text_attribute(blockquote(text), span));
+    insert_after_attributes(&mut docboxed, docbox, item.into_iter());
+
+    let mut result = TokenStream::new();
+    let mut item_iter = docboxed.into_iter();
+
+    // Insert the short box and the marker to gray the text together.
+    let mut short_docbox = short_docbox_html("unstable", "", None, "Experimental");
+    if !metadata.no_js {
+        short_docbox = short_docbox + SEMI_TRANSPARENT_HTML;
+    }
+    prepend_to_doc(
+        &mut result,
+        short_docbox,
+        &mut item_iter,
+        &bootstrap::assets(&metadata, "unstable", "", true),
+    );
+
+    result.extend(item_iter);
+
+    result
+}
+
+/// Marks an item as only being available on certain configurations.
+///
+/// This emulates the nightly-only
+/// [`doc_cfg`](https://doc.rust-lang.org/beta/unstable-book/language-features/doc-cfg.html)
+/// feature. The attribute accepts a single configuration predicate, written the same way as it
+/// would be for a `#[cfg]` attribute. The item is given a `"portability"` docbox describing the
+/// predicate, along with a short docbox in module lists.
+///
+/// ```
+/// #[doc_item::doc_cfg(feature = "foo")]
+/// pub fn foo() {}
+/// ```
+///
+/// The above will display "Available on **crate feature `foo`** only." in the item's docbox, the
+/// same as rustdoc would. Predicates may be combined using `all`, `any`, and `not`, and any
+/// configuration option may be used:
+///
+/// ```
+/// #[doc_item::doc_cfg(any(unix, all(target_os = "windows", target_pointer_width = "64")))]
+/// pub fn foo() {}
+/// ```
+///
+/// # Applying the Configuration
+///
+/// By default, only the documentation is affected. To also conditionally compile the item using
+/// the same predicate, provide the `apply` parameter after the predicate:
+///
+/// ```
+/// #[doc_item::doc_cfg(feature = "foo", apply)]
+/// pub fn foo() {}
+/// ```
+///
+/// This is equivalent to adding `#[cfg(feature = "foo")]` to the item, ensuring that the item's
+/// configuration and its documentation never diverge.
+///
+/// Note that the attribute can't be named `cfg` like the attribute it mirrors, as the compiler
+/// reserves that name in the attribute namespace, even when referred to by its full path.
+#[proc_macro_attribute]
+pub fn doc_cfg(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let span = attribute_span(&attr);
+    let attr_args = parse_macro_input!(attr as AttributeArgs);
+    let mut attr_iter = attr_args.iter();
+
+    let cfg = match attr_iter.next().map(Cfg::parse) {
+        Some(Ok(cfg)) => cfg,
+        Some(Err(err)) => {
+            return err.write_errors().into();
+        }
+        None => {
+            return darling::Error::custom("expected a `cfg` predicate")
+                .write_errors()
+                .into();
+        }
+    };
+    let mut apply = false;
+    for nested_meta in attr_iter {
+        match nested_meta {
+            NestedMeta::Meta(meta) if meta.path().is_ident("apply") && !apply => {
+                apply = true;
+            }
+            _ => {
+                return darling::Error::custom("expected `apply` after the `cfg` predicate")
+                    .with_span(nested_meta)
+                    .write_errors()
+                    .into();
+            }
+        }
+    }
+
+    let metadata = match load_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            return err.write_errors().into();
+        }
+    };
+
+    let mut docboxed = TokenStream::new();
+    if apply {
+        docboxed.extend(set_span(
+            TokenStream::from_str(&format!("#[cfg({})]", cfg.predicate())).unwrap(),
+            span,
+        ));
+    }
+    let (item, bootstrap) =
+        appended_assets(item, bootstrap::assets(&metadata, "portability", "", false));
+    let mut docbox = html_only(set_span(
+        markdown_docbox_attributes(
+            "<div class='stab portability'>",
+            doc_attribute(cfg.render_long_markdown()),
+            "</div>",
+            &bootstrap,
+            metadata.no_js,
+        ),
+        span,
+    ));
+    docbox.extend(text_attribute(blockquote(cfg.render_long_markdown()), span));
+    insert_after_attributes(&mut docboxed, docbox, item.into_iter());
+
+    let mut result = TokenStream::new();
+    let mut item_iter = docboxed.into_iter();
+
+    // Insert the short box.
+    prepend_to_doc(
+        &mut result,
+        short_docbox_html(
+            "portability",
+            "",
+            Some(&cfg.render_long_plain()),
+            cfg.render_short_html(),
+        ),
+        &mut item_iter,
+        &bootstrap::assets(&metadata, "portability", "", true),
+    );
+
+    result.extend(item_iter);
 
     result
 }
+
+/// Provides styles for the crate's own classes.
+///
+/// The CSS is included in the generated documentation along with the markup of each docbox and
+/// short docbox using a class other than rustdoc's own, so that the classes are styled on every page
+/// displaying them, without any additional rustdoc flags:
+///
+/// ```
+/// doc_item::styles!(".custom { background: #c4ffd7; border-color: #7bdba1; }");
+///
+/// #[doc_item::docbox(content="A custom docbox", class="custom")]
+/// pub fn foo() {}
+/// # fn main() {}
+/// ```
+///
+/// The CSS is not included once per page, as rustdoc provides no way to do so. Each item using it
+/// includes its own copy, as does each module list row summarizing such an item. The
+/// `doc_item-postprocess` tool keeps a single copy in the page's head.
+///
+/// The macro must be invoked at the top level of the crate root, such as `src/lib.rs`, where the
+/// attributes can find it, and fails to compile anywhere else. Each invocation's CSS is included in
+/// order. The attributes find the invocations by reading the crate root's source without expanding
+/// it, so:
+///
+/// - an invocation disabled by a `#[cfg]` attribute is still included, and
+/// - invocations generated by other macros are not found.
+#[proc_macro]
+pub fn styles(input: TokenStream) -> TokenStream {
+    static INVOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    let css = parse_macro_input!(input as LitStr);
+
+    // The expansion refers to itself through the crate root, which only resolves if it is at the
+    // top level of the crate root. Each invocation's item is named uniquely.
+    let name = format!(
+        "STYLES_MUST_BE_INVOKED_AT_THE_TOP_LEVEL_OF_THE_CRATE_ROOT_{}",
+        INVOCATIONS.fetch_add(1, Ordering::Relaxed)
+    );
+    set_span(
+        TokenStream::from_str(&format!(
+            "const {name}: () = (); const _: () = crate::{name};"
+        ))
+        .unwrap(),
+        css.span().unwrap(),
+    )
+}
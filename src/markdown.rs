@@ -0,0 +1,93 @@
+//! Rendering of Markdown content.
+
+use pulldown_cmark::{html, Event, Parser, Tag};
+
+/// Renders Markdown to HTML suitable for placing inline within a docbox.
+///
+/// Paragraph tags are omitted, so that the rendered content flows within the surrounding element.
+/// Inline HTML within the Markdown is passed through unchanged if `raw_html` is set, and is
+/// otherwise escaped and rendered as text.
+pub fn render_inline(markdown: &str, raw_html: bool) -> String {
+    let mut rendered = String::with_capacity(markdown.len());
+    html::push_html(
+        &mut rendered,
+        Parser::new(markdown).filter_map(|event| match event {
+            Event::Start(Tag::Paragraph) | Event::End(Tag::Paragraph) => None,
+            Event::Html(html) if !raw_html => Some(Event::Text(html)),
+            event => Some(event),
+        }),
+    );
+    rendered.trim_end().to_owned()
+}
+
+/// Escapes any HTML within Markdown, so that it is rendered as text.
+///
+/// Every ASCII punctuation character within the HTML is backslash-escaped, which prevents the
+/// escaped HTML from being interpreted as any other Markdown construct. Returns `None` if the
+/// Markdown contains no HTML.
+pub fn escape_html(markdown: &str) -> Option<String> {
+    let mut escaped = String::with_capacity(markdown.len());
+    let mut position = 0;
+    for (_, range) in Parser::new(markdown)
+        .into_offset_iter()
+        .filter(|(event, _)| matches!(event, Event::Html(_)))
+    {
+        escaped.push_str(&markdown[position..range.start]);
+        for c in markdown[range.clone()].chars() {
+            if c.is_ascii_punctuation() {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        position = range.end;
+    }
+    if position == 0 {
+        return None;
+    }
+    escaped.push_str(&markdown[position..]);
+    Some(escaped)
+}
+
+/// Escapes plain text, so that it is rendered unchanged within a Markdown paragraph.
+///
+/// Only the characters that can begin inline Markdown constructs are backslash-escaped, keeping the
+/// escaped text readable when it is displayed without being rendered.
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>&~|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Formats text as a Markdown code span.
+///
+/// The code span is delimited by a longer run of backticks than any within the text, and is padded
+/// with spaces if the text begins or ends with a backtick.
+pub fn code_span(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let delimiter = "`".repeat(longest + 1);
+    let padding = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{delimiter}{padding}{text}{padding}{delimiter}")
+}
+
+/// Collects all of the HTML within Markdown.
+pub fn html(markdown: &str) -> String {
+    Parser::new(markdown)
+        .filter_map(|event| match event {
+            Event::Html(html) => Some(html.into_string()),
+            _ => None,
+        })
+        .collect()
+}
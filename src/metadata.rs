@@ -0,0 +1,281 @@
+//! Crate-level configuration read from the documented crate's manifest.
+//!
+//! Configuration is provided in the `[package.metadata.doc_item]` table of the `Cargo.toml` of the
+//! crate being documented. The manifest is located using the `CARGO_MANIFEST_DIR` environment
+//! variable, which Cargo sets when invoking the compiler.
+//!
+//! The crate's own styles are provided separately, by the `styles!` macro within its crate root.
+//!
+//! Every attribute needs the configuration, so it is loaded once for each crate compiled by the
+//! process and shared by the attributes that follow. It is only loaded again if the manifest or
+//! the crate root has been modified since, as can happen when the process is kept running by an
+//! IDE.
+
+use crate::styles;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    env, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
+use toml::{value::Table, Value};
+
+/// Identifies a crate by the directory containing its manifest, along with its crate and binary
+/// names, as a package may contain many crates.
+type Key = (PathBuf, Option<String>, Option<String>);
+
+/// Configuration loaded previously, along with the files it was loaded from and their modification
+/// times at the time.
+struct Cached {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    metadata: Rc<Metadata>,
+}
+
+impl Cached {
+    /// Returns whether none of the files have been modified since the configuration was loaded.
+    fn is_fresh(&self) -> bool {
+        self.files
+            .iter()
+            .all(|(path, time)| modified(path) == *time)
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<Key, Cached>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the time the file was last modified, if it can be determined.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Configuration from the `[package.metadata.doc_item]` table.
+#[derive(Default)]
+pub struct Metadata {
+    /// Whether `since` values must be valid semantic versions no greater than the crate's version.
+    pub strict_since: bool,
+    /// Whether documentation is generated as static markup, without relying on scripts.
+    pub no_js: bool,
+    /// CSS provided by the `styles!` macro, included with the markup using the crate's own classes.
+    pub styles: Option<String>,
+    /// The URL of an issue in the crate's issue tracker, with `{}` in place of the issue number.
+    pub issue_url: Option<String>,
+    /// Named sets of docbox parameters, from the `[package.metadata.doc_item.presets]` table.
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// Docbox parameters defined once in the manifest and referred to by name using `preset`.
+///
+/// Each field is only used when the attribute referring to the preset doesn't provide it.
+#[derive(Default)]
+pub struct Preset {
+    /// The content of docboxes using the preset.
+    pub content: Option<String>,
+    /// The path of a file containing the content of docboxes using the preset, relative to the
+    /// directory containing the manifest.
+    pub content_file: Option<String>,
+    /// The content of short docboxes using the preset. Defaults to the `content` or `content_file`.
+    pub short: Option<String>,
+    pub class: Option<String>,
+    pub markdown: Option<bool>,
+    pub raw_html: Option<bool>,
+    pub background: Option<String>,
+    pub border: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub issue: Option<u32>,
+}
+
+impl Preset {
+    /// Parses the preset with the given name from its table in the manifest.
+    fn parse(name: &str, value: &Value) -> Result<Self, String> {
+        let path = format!("package.metadata.doc_item.presets.{name}");
+        let table = value
+            .as_table()
+            .ok_or_else(|| format!("`{path}` must be a table"))?;
+
+        let mut preset = Self::default();
+        for (key, value) in table {
+            let string = || {
+                value
+                    .as_str()
+                    .map(str::to_owned)
+                    .ok_or_else(|| format!("`{path}.{key}` must be a string"))
+            };
+            let boolean = || {
+                value
+                    .as_bool()
+                    .ok_or_else(|| format!("`{path}.{key}` must be a boolean"))
+            };
+            match key.as_str() {
+                "content" => preset.content = Some(string()?),
+                "content_file" => preset.content_file = Some(string()?),
+                "short" => preset.short = Some(string()?),
+                "class" => preset.class = Some(string()?),
+                "markdown" => preset.markdown = Some(boolean()?),
+                "raw_html" => preset.raw_html = Some(boolean()?),
+                "background" => preset.background = Some(string()?),
+                "border" => preset.border = Some(string()?),
+                "color" => preset.color = Some(string()?),
+                "icon" => preset.icon = Some(string()?),
+                "issue" => {
+                    preset.issue = Some(
+                        value
+                            .as_integer()
+                            .and_then(|issue| u32::try_from(issue).ok())
+                            .ok_or_else(|| format!("`{path}.{key}` must be an issue number"))?,
+                    );
+                }
+                _ => return Err(format!("unknown key `{path}.{key}`")),
+            }
+        }
+        if preset.content.is_some() && preset.content_file.is_some() {
+            return Err(format!(
+                "`{path}.content` and `{path}.content_file` cannot both be provided"
+            ));
+        }
+
+        Ok(preset)
+    }
+}
+
+impl Metadata {
+    /// Loads the configuration from the manifest of the crate currently being compiled, reusing the
+    /// configuration loaded previously for the same crate if it is still up to date.
+    ///
+    /// If the crate is not being compiled through Cargo, the default configuration is returned. If
+    /// its manifest contains no `[package.metadata.doc_item]` table, the default configuration is
+    /// used along with the crate's styles.
+    pub fn load() -> Result<Rc<Self>, String> {
+        let manifest_dir = match manifest_dir() {
+            Some(manifest_dir) => manifest_dir,
+            None => return Ok(Rc::default()),
+        };
+        let key = (
+            manifest_dir.clone(),
+            env::var("CARGO_CRATE_NAME").ok(),
+            env::var("CARGO_BIN_NAME").ok(),
+        );
+        let cached = CACHE.with(|cache| {
+            cache
+                .borrow()
+                .get(&key)
+                .filter(|cached| cached.is_fresh())
+                .map(|cached| Rc::clone(&cached.metadata))
+        });
+        if let Some(metadata) = cached {
+            return Ok(metadata);
+        }
+
+        let (metadata, files) = Self::read(&manifest_dir)?;
+        let metadata = Rc::new(metadata);
+        let cached = Cached {
+            files: files
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+            metadata: Rc::clone(&metadata),
+        };
+        CACHE.with(|cache| cache.borrow_mut().insert(key, cached));
+        Ok(metadata)
+    }
+
+    /// Reads the configuration from the manifest in the given directory, returning it along with
+    /// the paths of the files it was read from.
+    fn read(manifest_dir: &Path) -> Result<(Self, Vec<PathBuf>), String> {
+        let manifest_path = manifest_dir.join("Cargo.toml");
+        let manifest = fs::read_to_string(&manifest_path)
+            .map_err(|err| format!("unable to read `{}`: {err}", manifest_path.display()))?
+            .parse::<Value>()
+            .map_err(|err| format!("unable to parse `{}`: {err}", manifest_path.display()))?;
+
+        let mut metadata = match manifest
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("doc_item"))
+        {
+            Some(Value::Table(table)) => Self::parse(table)?,
+            Some(_) => return Err("`package.metadata.doc_item` must be a table".to_owned()),
+            None => Self::default(),
+        };
+        let mut files = vec![manifest_path];
+        if let Some(crate_root) = styles::crate_root(manifest_dir, &manifest) {
+            let crate_root = manifest_dir.join(crate_root);
+            metadata.styles = styles::load(&crate_root)?;
+            files.push(crate_root);
+        }
+
+        Ok((metadata, files))
+    }
+
+    /// Parses the configuration from the `[package.metadata.doc_item]` table.
+    fn parse(table: &Table) -> Result<Self, String> {
+        let mut metadata = Self::default();
+        for (key, value) in table {
+            match key.as_str() {
+                "strict_since" => {
+                    metadata.strict_since = value.as_bool().ok_or_else(|| {
+                        "`package.metadata.doc_item.strict_since` must be a boolean".to_owned()
+                    })?;
+                }
+                "no_js" => {
+                    metadata.no_js = value.as_bool().ok_or_else(|| {
+                        "`package.metadata.doc_item.no_js` must be a boolean".to_owned()
+                    })?;
+                }
+                "issue_url" => {
+                    let issue_url = value.as_str().ok_or_else(|| {
+                        "`package.metadata.doc_item.issue_url` must be a string".to_owned()
+                    })?;
+                    if !issue_url.contains("{}") {
+                        return Err(
+                            "`package.metadata.doc_item.issue_url` must contain `{}`, which is replaced by the issue number"
+                                .to_owned(),
+                        );
+                    }
+                    metadata.issue_url = Some(issue_url.to_owned());
+                }
+                "presets" => {
+                    let presets = value.as_table().ok_or_else(|| {
+                        "`package.metadata.doc_item.presets` must be a table".to_owned()
+                    })?;
+                    for (name, preset) in presets {
+                        metadata
+                            .presets
+                            .insert(name.clone(), Preset::parse(name, preset)?);
+                    }
+                }
+                _ => return Err(format!("unknown key `package.metadata.doc_item.{key}`")),
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// Returns the URL of the issue with the given number, if an issue tracker is configured.
+    pub fn issue_url(&self, issue: u32) -> Option<String> {
+        self.issue_url
+            .as_ref()
+            .map(|issue_url| issue_url.replace("{}", &issue.to_string()))
+    }
+}
+
+/// Returns the directory containing the manifest of the crate currently being compiled, if it is
+/// being compiled through Cargo.
+fn manifest_dir() -> Option<PathBuf> {
+    env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from)
+}
+
+/// Resolves a path relative to the directory containing the crate's manifest.
+///
+/// If the crate is not being compiled through Cargo, the path is left relative to the current
+/// directory.
+pub fn resolve(path: &str) -> PathBuf {
+    manifest_dir().map_or_else(|| Path::new(path).to_owned(), |dir| dir.join(path))
+}
@@ -0,0 +1,108 @@
+//! The built-in docbox classes.
+//!
+//! Each class is styled using a pair of CSS variables, `--doc-item-<class>-background` and
+//! `--doc-item-<class>-border`, defined separately for each of rustdoc's themes. Crates can override
+//! the variables within their own styles.
+//!
+//! Each class has its own stylesheet, which is only included with the markup using the class.
+
+use std::fmt::Write;
+
+/// The selectors matching each of rustdoc's themes, in the same order as the colors of each class.
+///
+/// rustdoc marks the active theme using the `data-theme` attribute of the root element. The light
+/// theme's colors are used as defaults, for when the attribute is missing. The selectors have no
+/// specificity, so that the variables can be overridden regardless of the order the stylesheets
+/// are included in.
+const THEMES: [&str; 3] = [
+    ":where(:root)",
+    ":where(:root[data-theme=\"dark\"])",
+    ":where(:root[data-theme=\"ayu\"])",
+];
+
+/// The built-in classes, along with their background and border colors in each theme.
+const CLASSES: [(&str, [(&str, &str); 3]); 6] = [
+    (
+        "info",
+        [
+            ("#dbeeff", "#6cb4f0"),
+            ("#1f3f5b", "#3d7ab3"),
+            ("#14324d", "#39afd7"),
+        ],
+    ),
+    (
+        "note",
+        [
+            ("#ece5ff", "#a88ce8"),
+            ("#3b3258", "#7a64b8"),
+            ("#2b2546", "#a37acc"),
+        ],
+    ),
+    (
+        "warning",
+        [
+            ("#fff0c7", "#f0b429"),
+            ("#5a4a1a", "#b08a1e"),
+            ("#3d3015", "#ffb454"),
+        ],
+    ),
+    (
+        "danger",
+        [
+            ("#ffdcdc", "#e06666"),
+            ("#5c2626", "#b04848"),
+            ("#3f1a1d", "#f07178"),
+        ],
+    ),
+    (
+        "success",
+        [
+            ("#d9f7df", "#5cc275"),
+            ("#1f4a2a", "#3f9a58"),
+            ("#1c3320", "#91b362"),
+        ],
+    ),
+    (
+        "security",
+        [
+            ("#fde2f3", "#d670b0"),
+            ("#532a47", "#a85590"),
+            ("#3a1c34", "#ff8fd1"),
+        ],
+    ),
+];
+
+/// Returns the built-in classes used by markup with the given classes and inline style, either
+/// directly or through their variables.
+pub fn used(class: &str, style: &str) -> Vec<&'static str> {
+    CLASSES
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| {
+            class.split_ascii_whitespace().any(|class| class == *name)
+                || style.contains(&format!("--doc-item-{name}-"))
+        })
+        .collect()
+}
+
+/// Returns the stylesheet defining one of the built-in classes.
+pub fn css(class: &str) -> String {
+    let (class, colors) = CLASSES
+        .iter()
+        .find(|(name, _)| *name == class)
+        .expect("not a built-in class");
+    let mut css = String::new();
+    for (theme, (background, border)) in THEMES.iter().zip(colors) {
+        write!(
+            css,
+            "{theme}{{--doc-item-{class}-background:{background};--doc-item-{class}-border:{border};}}"
+        )
+        .unwrap();
+    }
+    write!(
+        css,
+        ".stab.{class}{{background-color:var(--doc-item-{class}-background);border-color:var(--doc-item-{class}-border);}}"
+    )
+    .unwrap();
+    css
+}
@@ -0,0 +1,134 @@
+//! Placeholders within content, such as `{crate}`, expanded when the attribute is evaluated.
+//!
+//! Placeholders are names within braces. Literal braces are written by doubling them, as `{{` and
+//! `}}`, the same as in `format!` strings.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+use std::env;
+
+/// The names of the available placeholders.
+const NAMES: [&str; 4] = ["crate", "version", "item", "kind"];
+
+/// The name and kind of an item, such as `foo` and `function`.
+struct Item {
+    name: String,
+    kind: &'static str,
+}
+
+/// Returns the name and kind of the item, or `None` if it has no name, such as an `impl` block.
+///
+/// Only the tokens preceding the item's name are examined: its attributes, visibility, and
+/// qualifiers such as `const` or `unsafe` are skipped, and the name follows the keyword
+/// introducing the item.
+fn describe(item: &TokenStream) -> Option<Item> {
+    let mut tokens = item.clone().into_iter().peekable();
+    let kind = loop {
+        match tokens.next()? {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                tokens.next();
+            }
+            TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                "pub" => {
+                    if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+                    {
+                        tokens.next();
+                    }
+                }
+                "const" => match tokens.peek() {
+                    Some(TokenTree::Ident(next))
+                        if ["fn", "async", "unsafe", "extern"]
+                            .contains(&next.to_string().as_str()) => {}
+                    _ => break "constant",
+                },
+                "extern" => {
+                    if let Some(TokenTree::Literal(_)) = tokens.peek() {
+                        tokens.next();
+                    }
+                }
+                "static" => {
+                    if matches!(tokens.peek(), Some(TokenTree::Ident(next)) if next.to_string() == "mut")
+                    {
+                        tokens.next();
+                    }
+                    break "static";
+                }
+                "macro_rules" => {
+                    tokens.next();
+                    break "macro";
+                }
+                "async" | "unsafe" | "default" | "auto" => {}
+                "fn" => break "function",
+                "struct" => break "struct",
+                "enum" => break "enum",
+                "union" => break "union",
+                "trait" => break "trait",
+                "type" => break "type alias",
+                "mod" => break "module",
+                _ => return None,
+            },
+            _ => return None,
+        }
+    };
+    match tokens.next()? {
+        TokenTree::Ident(name) => {
+            let name = name.to_string();
+            Some(Item {
+                name: name.strip_prefix("r#").unwrap_or(&name).to_owned(),
+                kind,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the value of the placeholder with the given name.
+fn value(name: &str, item: &TokenStream) -> Result<String, String> {
+    match name {
+        "crate" => env::var("CARGO_PKG_NAME")
+            .map_err(|_| "`{crate}` is only available when compiling through Cargo".to_owned()),
+        "version" => env::var("CARGO_PKG_VERSION")
+            .map_err(|_| "`{version}` is only available when compiling through Cargo".to_owned()),
+        "item" | "kind" => describe(item)
+            .map(|item| {
+                if name == "item" {
+                    item.name
+                } else {
+                    item.kind.to_owned()
+                }
+            })
+            .ok_or_else(|| format!("`{{{name}}}` is not available, as the item has no name")),
+        _ => Err(format!(
+            "unknown placeholder `{{{name}}}`, expected one of {}; use `{{{{` and `}}}}` for literal braces",
+            NAMES
+                .iter()
+                .map(|name| format!("`{{{name}}}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Expands the placeholders within the text, using the item the attribute is applied to.
+pub fn expand(text: &str, item: &TokenStream) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}']) {
+        result.push_str(&rest[..index]);
+        let brace = &rest[index..=index];
+        let after = &rest[index + 1..];
+        if let Some(after) = after.strip_prefix(brace) {
+            result.push_str(brace);
+            rest = after;
+        } else if brace == "}" {
+            return Err("unmatched `}`; use `}}` for a literal brace".to_owned());
+        } else {
+            let end = after
+                .find('}')
+                .ok_or_else(|| "unclosed `{`; use `{{` for a literal brace".to_owned())?;
+            result.push_str(&value(&after[..end], item)?);
+            rest = &after[end + 1..];
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
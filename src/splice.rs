@@ -0,0 +1,175 @@
+//! Splicing of macro calls into generated documentation.
+//!
+//! A `content` such as `content = env!("FOO")` can only be evaluated by the compiler. The macro call
+//! is taken out of the attribute before the attribute is parsed, and is kept apart from the text of
+//! the generated documentation, so that no text can ever be mistaken for a call. It is then spliced
+//! into the emitted doc attributes as an argument to `concat!`, deferring its evaluation to the
+//! compiler.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::ops::Add;
+
+/// Takes the macro call given as the `content`, if any, out of the attribute's arguments.
+///
+/// The call is replaced by an empty string literal with the call's span, allowing the arguments to
+/// be parsed like any other.
+pub fn extract(attr: TokenStream) -> (TokenStream, Option<TokenStream>) {
+    let mut result = TokenStream::new();
+    let mut call = None;
+    let mut tokens = attr.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let is_content =
+            matches!(&token, TokenTree::Ident(ident) if ident.to_string() == "content");
+        result.extend(Some(token));
+        if !is_content {
+            continue;
+        }
+        match tokens.peek() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                result.extend(tokens.next());
+            }
+            _ => continue,
+        }
+
+        // Collect the value, up to the next comma.
+        let mut value = Vec::new();
+        while let Some(token) = tokens.peek() {
+            if matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',') {
+                break;
+            }
+            value.extend(tokens.next());
+        }
+        let is_macro_call = matches!(
+            value.as_slice(),
+            [.., TokenTree::Punct(punct), TokenTree::Group(_)] if punct.as_char() == '!'
+        );
+        if is_macro_call {
+            let mut literal = Literal::string("");
+            literal.set_span(value[0].span());
+            result.extend(Some(TokenTree::from(literal)));
+            call = Some(value.into_iter().collect());
+        } else {
+            result.extend(value);
+        }
+    }
+    (result, call)
+}
+
+/// A part of the generated documentation.
+#[derive(Clone)]
+enum Part {
+    Text(String),
+    Call(TokenStream),
+}
+
+/// Generated documentation, which may contain macro calls to be evaluated by the compiler.
+#[derive(Clone, Default)]
+pub struct Spliced {
+    parts: Vec<Part>,
+}
+
+impl Spliced {
+    /// Returns documentation consisting of the result of the macro call.
+    pub fn call(call: TokenStream) -> Self {
+        Self {
+            parts: vec![Part::Call(call)],
+        }
+    }
+
+    /// Returns whether there is no documentation at all.
+    pub fn is_empty(&self) -> bool {
+        self.parts
+            .iter()
+            .all(|part| matches!(part, Part::Text(text) if text.is_empty()))
+    }
+
+    /// Applies `f` to the text surrounding any macro calls.
+    ///
+    /// The result of a macro call is only known once the compiler evaluates it, so calls are left
+    /// as they are.
+    pub fn map_text(&self, f: impl Fn(&str) -> String) -> Self {
+        Self {
+            parts: self
+                .parts
+                .iter()
+                .map(|part| match part {
+                    Part::Text(text) => Part::Text(f(text)),
+                    Part::Call(call) => Part::Call(call.clone()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Converts the documentation into an expression usable as the value of a doc attribute.
+    ///
+    /// Documentation without any macro calls becomes a string literal. Otherwise, it becomes a call
+    /// to `concat!`, with the macro calls as arguments.
+    pub fn expression(&self) -> TokenStream {
+        match self.parts.as_slice() {
+            [] => TokenTree::from(Literal::string("")).into(),
+            [Part::Text(text)] => TokenTree::from(Literal::string(text)).into(),
+            parts => {
+                let mut arguments = TokenStream::new();
+                for part in parts {
+                    match part {
+                        Part::Text(text) => {
+                            arguments.extend(Some(TokenTree::from(Literal::string(text))));
+                        }
+                        Part::Call(call) => arguments.extend(call.clone()),
+                    }
+                    arguments.extend(Some(TokenTree::from(Punct::new(',', Spacing::Alone))));
+                }
+                let separator = || {
+                    vec![
+                        TokenTree::from(Punct::new(':', Spacing::Joint)),
+                        TokenTree::from(Punct::new(':', Spacing::Alone)),
+                    ]
+                };
+                let mut expression = separator();
+                expression.push(Ident::new("core", Span::call_site()).into());
+                expression.extend(separator());
+                expression.extend(vec![
+                    Ident::new("concat", Span::call_site()).into(),
+                    Punct::new('!', Spacing::Alone).into(),
+                    Group::new(Delimiter::Parenthesis, arguments).into(),
+                ]);
+                expression.into_iter().collect()
+            }
+        }
+    }
+}
+
+impl From<&str> for Spliced {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_owned())
+    }
+}
+
+impl From<String> for Spliced {
+    fn from(text: String) -> Self {
+        Self {
+            parts: vec![Part::Text(text)],
+        }
+    }
+}
+
+impl From<&Self> for Spliced {
+    fn from(spliced: &Self) -> Self {
+        spliced.clone()
+    }
+}
+
+impl<T: Into<Self>> Add<T> for Spliced {
+    type Output = Self;
+
+    /// Appends the documentation, merging adjacent text.
+    fn add(mut self, other: T) -> Self {
+        for part in other.into().parts {
+            match (self.parts.last_mut(), part) {
+                (Some(Part::Text(text)), Part::Text(other)) => text.push_str(&other),
+                (_, part) => self.parts.push(part),
+            }
+        }
+        self
+    }
+}
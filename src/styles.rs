@@ -0,0 +1,140 @@
+//! Crate-level styles provided by the `styles!` macro.
+//!
+//! The macro itself only checks where it is invoked, as there is no way for it to share its CSS
+//! with the attributes on other items. Instead, the attributes read the CSS from the invocations
+//! within the source of the crate root. The source is only tokenized, so `#[cfg]` attributes on the
+//! invocations are not evaluated, and invocations generated by other macros are not seen. The
+//! crate root is located using the manifest along with the `CARGO_CRATE_NAME` and `CARGO_BIN_NAME`
+//! environment variables, which Cargo sets when invoking the compiler.
+
+use proc_macro::{TokenStream, TokenTree};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use syn::LitStr;
+use toml::Value;
+
+/// The target tables of the manifest, other than the binaries', that may configure the path of a
+/// crate root.
+const TARGETS: [&str; 3] = ["example", "test", "bench"];
+
+/// The directories containing crate roots discovered by Cargo, other than the library's and the
+/// main binary's.
+const DIRECTORIES: [&str; 4] = ["src/bin", "examples", "tests", "benches"];
+
+/// Returns the path of the crate root currently being compiled, relative to the directory
+/// containing the manifest, or nothing if it can't be determined.
+pub fn crate_root<'a>(manifest_dir: &Path, manifest: &'a Value) -> Option<PathBuf> {
+    let crate_name = env::var("CARGO_CRATE_NAME").ok()?;
+    let bin_name = env::var("CARGO_BIN_NAME").ok();
+    let package_name = manifest.get("package")?.get("name")?.as_str()?;
+    let is_crate = |name: &str| name.replace('-', "_") == crate_name;
+
+    // Binaries are the only targets for which Cargo sets `CARGO_BIN_NAME`, and the library is named
+    // after the package unless its name is configured.
+    let name = |target: &'a Value| target.get("name").and_then(Value::as_str);
+    let lib = manifest.get("lib");
+    let lib_name = lib.and_then(name).unwrap_or(package_name);
+
+    // Targets configured with an explicit path.
+    let mut targets = Vec::new();
+    if bin_name.is_some() {
+        let bins = manifest.get("bin").and_then(Value::as_array);
+        targets.extend(bins.into_iter().flatten().map(|bin| (bin, name(bin))));
+    } else {
+        targets.extend(lib.map(|lib| (lib, Some(lib_name))));
+        let others = TARGETS
+            .iter()
+            .filter_map(|kind| manifest.get(kind)?.as_array())
+            .flatten();
+        targets.extend(others.map(|target| (target, name(target))));
+    }
+    if let Some(path) = targets
+        .into_iter()
+        .filter(|(_, name)| name.map_or(false, is_crate))
+        .find_map(|(target, _)| target.get("path")?.as_str())
+    {
+        return Some(PathBuf::from(path));
+    }
+
+    // Targets discovered from the standard layout.
+    let mut candidates = Vec::new();
+    match &bin_name {
+        None if is_crate(lib_name) => candidates.push(PathBuf::from("src/lib.rs")),
+        Some(bin_name) if bin_name == package_name => {
+            candidates.push(PathBuf::from("src/main.rs"));
+        }
+        _ => {}
+    }
+    let names = bin_name.map_or_else(
+        || vec![crate_name.clone(), crate_name.replace('_', "-")],
+        |bin_name| vec![bin_name],
+    );
+    for directory in &DIRECTORIES {
+        for name in &names {
+            candidates.push(Path::new(directory).join(format!("{name}.rs")));
+            candidates.push(Path::new(directory).join(name).join("main.rs"));
+        }
+    }
+    candidates
+        .into_iter()
+        .find(|candidate| manifest_dir.join(candidate).is_file())
+}
+
+/// Returns whether the tokens preceding `styles` leave the macro unqualified or qualify it as
+/// `doc_item::styles`.
+fn is_doc_item_path(preceding: &[TokenTree]) -> bool {
+    match preceding {
+        [.., TokenTree::Ident(ident), TokenTree::Punct(first), TokenTree::Punct(second)]
+            if first.as_char() == ':' && second.as_char() == ':' =>
+        {
+            ident.to_string() == "doc_item"
+        }
+        [.., TokenTree::Punct(punct)] if punct.as_char() == ':' => false,
+        _ => true,
+    }
+}
+
+/// Returns the CSS of each `styles!` invocation at the top level of the source.
+///
+/// Invocations not containing a string literal are skipped, as the macro reports them itself.
+fn find(source: &str) -> Result<Vec<String>, String> {
+    let tokens = TokenStream::from_str(source)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .collect::<Vec<_>>();
+    let mut styles = Vec::new();
+    for index in 0..tokens.len() {
+        if let [TokenTree::Ident(ident), TokenTree::Punct(punct), TokenTree::Group(group), ..] =
+            &tokens[index..]
+        {
+            if ident.to_string() == "styles"
+                && punct.as_char() == '!'
+                && is_doc_item_path(&tokens[..index])
+            {
+                if let Ok(css) = syn::parse::<LitStr>(group.stream()) {
+                    styles.push(css.value());
+                }
+            }
+        }
+    }
+    Ok(styles)
+}
+
+/// Loads the CSS provided by the `styles!` invocations within the crate root at the given path.
+///
+/// If the crate root contains no invocations, nothing is returned.
+pub fn load(path: &Path) -> Result<Option<String>, String> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("unable to read `{}`: {err}", path.display()))?;
+    // Avoid tokenizing crate roots that can't contain an invocation.
+    if !source.contains("styles") {
+        return Ok(None);
+    }
+    let styles = find(&source)
+        .map_err(|err| format!("unable to parse `{}`: {err}", path.display()))?
+        .concat();
+    Ok(Some(styles).filter(|styles| !styles.is_empty()))
+}
@@ -5,10 +5,12 @@ fn test_docbox(driver: &WebDriver, prev_element_text: &str) {
     // Check contents.
     let item_info = driver
         .find_element(By::ClassName("item-info"))
-        .expect(&format!(
-            "Couldn't find element with previous element text: {}",
-            prev_element_text
-        ));
+        .unwrap_or_else(|_| {
+            panic!(
+                "Couldn't find element with previous element text: {}",
+                prev_element_text
+            )
+        });
     assert_eq!(
         item_info
             .outer_html()
@@ -18,10 +20,12 @@ fn test_docbox(driver: &WebDriver, prev_element_text: &str) {
     // Check location.
     let prev_element = item_info
         .find_element(By::XPath("./preceding-sibling::*[1]"))
-        .expect(&format!(
-            "Couldn't find previous element with text: {}",
-            prev_element_text
-        ));
+        .unwrap_or_else(|_| {
+            panic!(
+                "Couldn't find previous element with text: {}",
+                prev_element_text
+            )
+        });
     assert!(
         prev_element
             .text()
@@ -30,21 +34,45 @@ fn test_docbox(driver: &WebDriver, prev_element_text: &str) {
     );
 }
 
+/// Finds the since element, checking that it was moved next to the item's heading.
+fn find_since_heading(driver: &WebDriver) -> WebElement<'_> {
+    let since = driver
+        .find_element(By::ClassName("since"))
+        .expect("Couldn't find since element");
+    // The heading's right side is `.out-of-band` in older versions of rustdoc and `.sub-heading`
+    // in newer ones.
+    since
+        .find_element(By::XPath(
+            "./parent::*[contains(@class, 'out-of-band') or contains(@class, 'sub-heading')]",
+        ))
+        .expect("Couldn't find since's heading");
+    assert!(
+        since
+            .find_element(By::XPath("./preceding-sibling::*"))
+            .is_err(),
+        "since is not the heading's first element"
+    );
+    since
+}
+
 fn test_since_out_of_band(driver: &WebDriver) {
-    let out_of_band = driver
-        .find_element(By::ClassName("out-of-band"))
-        .expect("Couldn't find out-of-band element");
-    let first_child_element = out_of_band
-        .find_element(By::XPath("./child::*[1]"))
-        .expect("Couldn't find first child of out-of-band element");
     assert_eq!(
-        first_child_element
+        find_since_heading(driver)
             .outer_html()
-            .expect("Couldn't find child element's outer HTML"),
+            .expect("Couldn't find since's outer HTML"),
         "<span class=\"since\">1.0.0</span>"
     );
 }
 
+fn test_stable_const_since(driver: &WebDriver) {
+    assert_eq!(
+        find_since_heading(driver)
+            .outer_html()
+            .expect("Couldn't find since's outer HTML"),
+        "<span class=\"since\" title=\"Stable since version 1.0.0, const since 1.1.0\">const: 1.1.0 · 1.0.0</span>"
+    );
+}
+
 fn test_since_standalone(driver: &WebDriver) {
     let since = driver
         .find_element(By::ClassName("since"))
@@ -55,15 +83,26 @@ fn test_since_standalone(driver: &WebDriver) {
             .expect("Couldn't get outer HTML of since"),
         "<span class=\"since\">1.0.0</span>"
     );
-    let next_element = since
-        .find_element(By::XPath("./following-sibling::*[1][@class=\"srclink\"]"))
+    // The source link is `.srclink` in older versions of rustdoc and `a.src` in newer ones.
+    since
+        .find_element(By::XPath(
+            "./following-sibling::*[1][@class=\"srclink\" or (self::a and contains(@class, 'src'))]",
+        ))
         .expect("Couldn't find since's next element");
 }
 
+/// Checks that all of the generated markup was placed, and that the bootstraps removed themselves.
+fn test_placed(driver: &WebDriver) {
+    assert!(driver
+        .find_elements(By::Css("[data-doc-item], script[data-doc-item-bootstrap]"))
+        .expect("Couldn't search for markup")
+        .is_empty());
+}
+
 fn test_short_docbox(driver: &WebDriver, link_text: &str) {
     let link = driver
         .find_element(By::LinkText(link_text))
-        .expect(&format!("Couldn't find link with text {}", link_text));
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
     let span = link
         .find_element(By::XPath("./following-sibling::*[1]"))
         .expect("Couldn't find span");
@@ -75,10 +114,146 @@ fn test_short_docbox(driver: &WebDriver, link_text: &str) {
     );
 }
 
+fn test_unstable_docbox(driver: &WebDriver) {
+    let item_info = driver
+        .find_element(By::ClassName("item-info"))
+        .expect("Couldn't find item-info element");
+    assert_eq!(
+        item_info
+            .outer_html()
+            .expect("Couldn't obtain item-info's outer HTML"),
+        "<div class=\"item-info\"><div class=\"stab unstable\"><span class=\"emoji\">🔬</span> This is an experimental API. (<code>feature</code>&nbsp;<a href=\"https://example.com/issues/1?tracker=a&amp;b\">#1</a>)</div></div>"
+    );
+}
+
+fn test_unstable_short_docbox(driver: &WebDriver, link_text: &str) {
+    let link = driver
+        .find_element(By::LinkText(link_text))
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
+    let span = link
+        .find_element(By::XPath("./following-sibling::*[1]"))
+        .expect("Couldn't find span");
+    assert_eq!(
+        span
+            .outer_html()
+            .expect("Couldn't get span's outer HTML"),
+        "<span class=\"stab unstable\">Experimental</span>"
+    );
+}
+
+fn test_cfg_docbox(driver: &WebDriver) {
+    let item_info = driver
+        .find_element(By::ClassName("item-info"))
+        .expect("Couldn't find item-info element");
+    assert_eq!(
+        item_info
+            .outer_html()
+            .expect("Couldn't obtain item-info's outer HTML"),
+        "<div class=\"item-info\"><div class=\"stab portability\">Available on <strong>crate feature <code>foo</code></strong> only.</div></div>"
+    );
+}
+
+fn test_cfg_short_docbox(driver: &WebDriver, link_text: &str) {
+    let link = driver
+        .find_element(By::LinkText(link_text))
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
+    let span = link
+        .find_element(By::XPath("./following-sibling::*[1]"))
+        .expect("Couldn't find span");
+    assert_eq!(
+        span
+            .outer_html()
+            .expect("Couldn't get span's outer HTML"),
+        "<span class=\"stab portability\" title=\"Available on crate feature `foo` only\"><code>foo</code></span>"
+    );
+}
+
+fn test_markdown_docbox(driver: &WebDriver) {
+    let item_info = driver
+        .find_element(By::ClassName("item-info"))
+        .expect("Couldn't find item-info element");
+    assert_eq!(
+        item_info
+            .outer_html()
+            .expect("Couldn't obtain item-info's outer HTML"),
+        "<div class=\"item-info\"><div class=\"stab docbox\">Use <code>function</code> <em>instead</em>, see <a href=\"https://example.com\">here</a>.</div></div>"
+    );
+}
+
+fn test_intra_doc_link_docbox(driver: &WebDriver) {
+    let item_info = driver
+        .find_element(By::ClassName("item-info"))
+        .expect("Couldn't find item-info element");
+    assert_eq!(
+        item_info
+            .outer_html()
+            .expect("Couldn't obtain item-info's outer HTML"),
+        "<div class=\"item-info\"><div class=\"stab docbox\">Use <a href=\"fn.function.html\" title=\"fn test_target::function\"><code>function</code></a> instead.</div></div>"
+    );
+}
+
+fn test_html_docbox(driver: &WebDriver, content: &str) {
+    let item_info = driver
+        .find_element(By::ClassName("item-info"))
+        .expect("Couldn't find item-info element");
+    assert_eq!(
+        item_info
+            .outer_html()
+            .expect("Couldn't obtain item-info's outer HTML"),
+        format!(
+            "<div class=\"item-info\"><div class=\"stab docbox\">{}</div></div>",
+            content
+        )
+    );
+}
+
+fn test_html_short_docbox(driver: &WebDriver, link_text: &str, content: &str) {
+    let link = driver
+        .find_element(By::LinkText(link_text))
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
+    let span = link
+        .find_element(By::XPath("./following-sibling::*[1]"))
+        .expect("Couldn't find span");
+    assert_eq!(
+        span
+            .outer_html()
+            .expect("Couldn't get span's outer HTML"),
+        format!("<span class=\"stab short-docbox\">{}</span>", content)
+    );
+}
+
+fn test_markdown_short_docbox(driver: &WebDriver, link_text: &str) {
+    let link = driver
+        .find_element(By::LinkText(link_text))
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
+    let span = link
+        .find_element(By::XPath("./following-sibling::*[1]"))
+        .expect("Couldn't find span");
+    assert_eq!(
+        span
+            .outer_html()
+            .expect("Couldn't get span's outer HTML"),
+        "<span class=\"stab short-docbox\"><em>Markdown</em></span>"
+    );
+}
+
+fn test_summary(driver: &WebDriver, link_text: &str, summary: &str) {
+    let link = driver
+        .find_element(By::LinkText(link_text))
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
+    let docblock = link
+        .find_element(By::XPath("./parent::*[1]/following-sibling::*[1]"))
+        .expect("Couldn't find docblock-short");
+    assert!(docblock
+        .text()
+        .expect("Couldn't obtain docblock-short's text")
+        .contains(summary));
+}
+
 fn test_semi_transparent_item(driver: &WebDriver, link_text: &str) {
     let link = driver
         .find_element(By::LinkText(link_text))
-        .expect(&format!("Couldn't find link with text {}", link_text));
+        .unwrap_or_else(|_| panic!("Couldn't find link with text {}", link_text));
     let docblock = link
         .find_element(By::XPath("./parent::*[1]"))
         .expect("Couldn't find module-item");
@@ -112,56 +287,62 @@ fn frontend() {
 
     // Test individual doc pages.
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("fn.function.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub fn function()");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("struct.Struct.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub struct Struct {}");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("enum.Enum.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub enum Enum {}");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("constant.CONST.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub const CONST: usize = 0;");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("static.STATIC.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub static STATIC: usize");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("union.Union.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(
         &driver,
         "pub union Union",
@@ -169,57 +350,145 @@ fn frontend() {
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("struct.Method.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub fn method()");
     test_since_standalone(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("trait.Trait.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "pub trait Trait { }");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("struct.ImplTrait.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "impl Trait for ImplTrait");
     test_since_standalone(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("module/index.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "Module test_target::module");
     test_since_out_of_band(&driver);
 
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("type.Type.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_docbox(&driver, "type Type = usize;");
     test_since_out_of_band(&driver);
 
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.unstable_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_unstable_docbox(&driver);
+
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.const_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_stable_const_since(&driver);
+
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.cfg_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_cfg_docbox(&driver);
+
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.markdown_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_markdown_docbox(&driver);
+
+    // Test intra-doc link docbox page.
+    driver
+        .get(format!(
+            "file://{}",
+            base_url
+                .join("fn.intra_doc_link_function.html")
+                .to_str()
+                .unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_intra_doc_link_docbox(&driver);
+
+    // Test escaped HTML docbox page.
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.escaped_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_html_docbox(&driver, "Prefer &lt;b&gt;bold&lt;/b&gt; &amp; ‘quotes’");
+
+    // Test raw HTML docbox page.
+    driver
+        .get(format!(
+            "file://{}",
+            base_url.join("fn.raw_html_function.html").to_str().unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_html_docbox(&driver, "<b>Raw</b>");
+
+    // Test macro content docbox page.
+    driver
+        .get(format!(
+            "file://{}",
+            base_url
+                .join("fn.macro_content_function.html")
+                .to_str()
+                .unwrap()
+        ))
+        .unwrap();
+    test_placed(&driver);
+    test_html_docbox(&driver, "Version 0.1.0");
+
     // Test main doc page.
     driver
-        .get(&format!(
+        .get(format!(
             "file://{}",
             base_url.join("index.html").to_str().unwrap()
         ))
         .unwrap();
+    test_placed(&driver);
     test_short_docbox(&driver, "function");
     test_short_docbox(&driver, "Struct");
     test_short_docbox(&driver, "Enum");
@@ -239,4 +508,20 @@ fn frontend() {
     test_semi_transparent_item(&driver, "Trait");
     test_semi_transparent_item(&driver, "module");
     test_semi_transparent_item(&driver, "Type");
+
+    test_unstable_short_docbox(&driver, "unstable_function");
+    test_semi_transparent_item(&driver, "unstable_function");
+
+    test_cfg_short_docbox(&driver, "cfg_function");
+
+    test_markdown_short_docbox(&driver, "markdown_function");
+    test_html_short_docbox(&driver, "escaped_function", "&lt;i&gt;Escaped&lt;/i&gt;");
+    test_html_short_docbox(&driver, "raw_html_function", "<i>Raw</i>");
+    test_html_short_docbox(&driver, "raw_doc_function", "Raw");
+
+    test_html_short_docbox(&driver, "include_str_function", "Included");
+    test_summary(&driver, "include_str_function", "Included docs.");
+    test_semi_transparent_item(&driver, "concat_function");
+    test_summary(&driver, "concat_function", "Concatenated docs.");
+    test_html_short_docbox(&driver, "macro_content_function", "test_target");
 }
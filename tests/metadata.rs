@@ -0,0 +1,36 @@
+mod util;
+
+fn check(fixture: &str) -> String {
+    util::cargo(
+        "check",
+        &format!("tests/metadata/{}/Cargo.toml", fixture),
+        "target/metadata",
+    )
+}
+
+#[test]
+fn strict_since() {
+    let output = check("strict_since");
+    let errors = util::errors(&output);
+
+    assert_eq!(errors.len(), 3, "{}", output);
+    assert!(
+        errors[0].starts_with("src/lib.rs:4:19: error: `1.2,0` is not a valid semantic version"),
+        "{}",
+        output
+    );
+    assert!(
+        errors[1].starts_with(
+            "src/lib.rs:7:19: error: version `2.0.0` is greater than the crate's version `1.1.0`"
+        ),
+        "{}",
+        output
+    );
+    assert!(
+        errors[2].starts_with(
+            "src/lib.rs:16:37: error: version `3.0.0` is greater than the crate's version `1.1.0`"
+        ),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,17 @@
+#[doc_item::since(content = "1.0.0")]
+pub fn valid() {}
+
+#[doc_item::since(content = "1.2,0")]
+pub fn malformed() {}
+
+#[doc_item::since(content = "2.0.0")]
+pub fn future() {}
+
+#[doc_item::since(content = "Before time began", free_form)]
+pub fn free_form() {}
+
+#[doc_item::stable(since = "1.0.0", const_since = "1.1.0")]
+pub const fn valid_const() {}
+
+#[doc_item::stable(since = "1.0.0", const_since = "3.0.0")]
+pub const fn future_const() {}
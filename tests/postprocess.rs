@@ -0,0 +1,181 @@
+mod util;
+
+use scraper::{ElementRef, Html, Selector};
+use std::{fs, process::Command, sync::Once};
+use util::TEST_TARGET;
+
+const TARGET_DIR: &str = "target/postprocess";
+
+/// Generates the documentation and processes it, once for all of the tests.
+fn postprocess() {
+    static POSTPROCESS: Once = Once::new();
+    POSTPROCESS.call_once(|| {
+        // Previously processed documentation must be regenerated.
+        let _ = fs::remove_dir_all(format!("{}/doc/test_target", TARGET_DIR));
+        let output = util::cargo("doc", TEST_TARGET, TARGET_DIR);
+        assert!(util::errors(&output).is_empty(), "{}", output);
+
+        let output = Command::new(env!("CARGO_BIN_EXE_doc_item-postprocess"))
+            .arg(format!("{}/doc/test_target", TARGET_DIR))
+            .output()
+            .expect("Could not run `doc_item-postprocess`");
+        assert!(output.status.success());
+        let output = String::from_utf8(output.stderr)
+            .expect("`doc_item-postprocess` output was not valid UTF-8");
+        assert!(output.is_empty(), "{}", output);
+    });
+}
+
+fn page(name: &str) -> String {
+    util::page(TARGET_DIR, "test_target", name)
+}
+
+/// Parses the page, so that the placement of markup can be checked independently of the exact
+/// markup used by each version of rustdoc.
+fn parse(page: &str) -> Html {
+    Html::parse_document(page)
+}
+
+fn select<'a>(document: &'a Html, selector: &str) -> ElementRef<'a> {
+    document
+        .select(&Selector::parse(selector).unwrap())
+        .next()
+        .unwrap_or_else(|| panic!("Couldn't find `{}`: {}", selector, document.html()))
+}
+
+fn matches(element: ElementRef, selector: &str) -> bool {
+    Selector::parse(selector).unwrap().matches(&element)
+}
+
+fn parent(element: ElementRef) -> Option<ElementRef> {
+    element.parent().and_then(ElementRef::wrap)
+}
+
+fn next_element_sibling(element: ElementRef) -> Option<ElementRef> {
+    element.next_siblings().find_map(ElementRef::wrap)
+}
+
+fn previous_element_sibling(element: ElementRef) -> Option<ElementRef> {
+    element.prev_siblings().find_map(ElementRef::wrap)
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect()
+}
+
+#[test]
+fn relocation() {
+    postprocess();
+
+    for name in &["index.html", "fn.function.html", "struct.Method.html"] {
+        let page = page(name);
+        assert!(
+            !page.contains("data-doc-item=") && !page.contains("data-doc-item-bootstrap"),
+            "{}: {}",
+            name,
+            page
+        );
+    }
+
+    // The docbox is placed before the item's documentation, and the since value is placed first
+    // in the heading's right side.
+    let function = parse(&page("fn.function.html"));
+    let item_info = select(&function, ".item-info");
+    assert_eq!(
+        item_info.html(),
+        "<div class=\"item-info\"><div class=\"stab docbox\">docbox content</div></div>"
+    );
+    assert!(
+        next_element_sibling(item_info).map_or(false, |next| matches(next, ".top-doc, .docblock")),
+        "{}",
+        function.html()
+    );
+    let since = select(&function, ".since");
+    assert_eq!(text(since), "1.0.0");
+    assert!(
+        parent(since).map_or(false, |parent| matches(
+            parent,
+            ".sub-heading, .out-of-band"
+        )) && previous_element_sibling(since).is_none(),
+        "{}",
+        function.html()
+    );
+
+    // The short docbox is placed after the item's name, which is semi-transparent.
+    let index = parse(&page("index.html"));
+    let link = select(&index, "a[href='fn.function.html']");
+    let short_docbox = next_element_sibling(link).expect("Couldn't find short docbox");
+    assert_eq!(
+        short_docbox.html(),
+        "<span class=\"stab short-docbox\">short docbox content</span>"
+    );
+    let name = parent(link).unwrap();
+    assert!(
+        matches(name, ".unstable[style='opacity:0.65']"),
+        "{}",
+        name.html()
+    );
+
+    // Within implementations, the since value is placed before the source link.
+    let method = parse(&page("struct.Method.html"));
+    let since = select(&method, ".since");
+    assert_eq!(text(since), "1.0.0");
+    assert!(
+        parent(since).map_or(false, |parent| matches(parent, ".rightside"))
+            && next_element_sibling(since).map_or(false, |next| matches(next, ".srclink, .src")),
+        "{}",
+        method.html()
+    );
+    let item_info = select(&method, ".item-info");
+    assert_eq!(
+        item_info.html(),
+        "<div class=\"item-info\"><div class=\"stab docbox\">docbox content</div></div>"
+    );
+    assert!(
+        next_element_sibling(item_info).map_or(false, |next| matches(next, ".docblock")),
+        "{}",
+        method.html()
+    );
+}
+
+#[test]
+fn styles() {
+    postprocess();
+
+    // A single copy of each stylesheet is moved into the head.
+    for name in &[
+        "index.html",
+        "fn.function.html",
+        "fn.inline_style_function.html",
+    ] {
+        let document = parse(&page(name));
+        let mut hrefs = Vec::new();
+        for link in document.select(&Selector::parse("link[data-doc-item-styles]").unwrap()) {
+            assert!(
+                parent(link).map_or(false, |parent| matches(parent, "head")),
+                "{}: {}",
+                name,
+                document.html()
+            );
+            let href = link.attr("href").unwrap().to_owned();
+            assert!(!hrefs.contains(&href), "{}: {}", name, href);
+            hrefs.push(href);
+        }
+        assert!(
+            hrefs
+                .iter()
+                .any(|href| href.ends_with(",.custom { background: %23c4ffd7; }")),
+            "{}: {:?}",
+            name,
+            hrefs
+        );
+    }
+
+    // Markup without any classes doesn't use the crate's styles.
+    let const_function = page("fn.const_function.html");
+    assert!(
+        !const_function.contains("data-doc-item-styles"),
+        "{}",
+        const_function
+    );
+}
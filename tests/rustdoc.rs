@@ -0,0 +1,574 @@
+mod util;
+
+use std::{fs, process::Command};
+use util::TEST_TARGET;
+
+const TARGET_DIR: &str = "target/rustdoc";
+
+/// Returns the manifest of a crate used by tests requiring their own configuration.
+fn fixture(name: &str) -> String {
+    format!("tests/rustdoc/{}/Cargo.toml", name)
+}
+
+fn doc(manifest_path: &str) -> String {
+    util::cargo("doc", manifest_path, TARGET_DIR)
+}
+
+/// Generates the documentation in rustdoc's JSON format, which is unstable, so nightly features are
+/// enabled regardless of the toolchain.
+fn json() -> String {
+    let status = Command::new("cargo")
+        .arg("rustdoc")
+        .arg("--manifest-path")
+        .arg(TEST_TARGET)
+        .arg("--quiet")
+        .arg("--")
+        .arg("-Zunstable-options")
+        .arg("--output-format")
+        .arg("json")
+        .env("CARGO_TARGET_DIR", TARGET_DIR)
+        .env("RUSTC_BOOTSTRAP", "1")
+        .status()
+        .expect("Could not run `cargo rustdoc`");
+    assert!(status.success());
+    fs::read_to_string(format!("{}/doc/test_target.json", TARGET_DIR))
+        .expect("Could not read generated JSON")
+}
+
+fn page(name: &str, page: &str) -> String {
+    util::page(TARGET_DIR, name, page)
+}
+
+/// Removes the copies of the stylesheet included with the generated markup.
+fn without_styles(page: &str) -> String {
+    let mut result = String::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<link rel='stylesheet' data-doc-item-styles") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        rest = &rest[rest.find('>').unwrap() + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Returns the stylesheets included with the generated markup.
+fn stylesheets(page: &str) -> Vec<&str> {
+    page.match_indices("<link rel='stylesheet' data-doc-item-styles href='data:text/css,")
+        .map(|(start, _)| &page[start..start + page[start..].find('>').unwrap() + 1])
+        .collect()
+}
+
+/// Returns the copies of the list bootstrap, or of the page bootstrap, included with the generated
+/// markup.
+fn bootstraps(page: &str, list: bool) -> Vec<&str> {
+    page.match_indices("<script data-doc-item-bootstrap")
+        .map(|(start, _)| &page[start..start + page[start..].find("</script>").unwrap()])
+        .filter(|bootstrap| bootstrap.contains("previousElementSibling") == list)
+        .collect()
+}
+
+/// Returns the summary of `function` in the module list.
+fn function_summary(index: &str) -> &str {
+    let start = index.find("href=\"fn.function.html\"").unwrap();
+    let start = start + index[start..].find("<dd>").unwrap() + "<dd>".len();
+    &index[start..start + index[start..].find("</dd>").unwrap()]
+}
+
+/// Returns the documentation of the page's item.
+fn docblock(page: &str) -> &str {
+    let start = page.find("<div class=\"docblock\">").unwrap();
+    &page[start..start + page[start..].find("</div></details>").unwrap()]
+}
+
+#[test]
+fn spans() {
+    let output = doc(&fixture("spans"));
+    let warnings = util::warnings(&output);
+
+    assert_eq!(warnings.len(), 4, "{}", output);
+    assert!(
+        warnings[0].starts_with("src/lib.rs:3:16: warning: unresolved link to `Missing`"),
+        "{}",
+        output
+    );
+    assert!(
+        warnings[1].starts_with("src/lib.rs:9:16: warning: unresolved link to `Missing`"),
+        "{}",
+        output
+    );
+    assert!(
+        warnings[2].starts_with("src/lib.rs:15:16: warning: unresolved link to `Missing`"),
+        "{}",
+        output
+    );
+    assert!(
+        warnings[3].starts_with("src/lib.rs:19:1: warning: unresolved link to `Missing`"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn no_js() {
+    let output = doc(&fixture("no_js"));
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    for name in &[
+        "index.html",
+        "fn.function.html",
+        "fn.unstable_function.html",
+        "fn.cfg_function.html",
+    ] {
+        let page = page("no_js", name);
+        assert!(
+            !page.contains("data-doc-item='docbox'") && !page.contains("data-doc-item='since'"),
+            "{}: {}",
+            name,
+            page
+        );
+        assert!(
+            !page.contains("data-doc-item-bootstrap"),
+            "{}: {}",
+            name,
+            page
+        );
+        assert!(page.contains("data-doc-item-styles"), "{}: {}", name, page);
+    }
+
+    let function = page("no_js", "fn.function.html");
+    // The short docbox is hidden on the item's own page by a stylesheet, which is the only thing
+    // hiding it. Without the stylesheet, it remains at the start of the item's summary.
+    assert!(
+        function.contains(".docblock [data-doc-item=short-docbox]{display:none}"),
+        "{}",
+        function
+    );
+    let function = without_styles(&function);
+    assert_eq!(
+        function
+            .matches("<span class='stab unstable' data-doc-item='short-docbox'>")
+            .count(),
+        1,
+        "{}",
+        function
+    );
+    assert!(
+        function.contains(
+            "<p><span class='stab unstable' data-doc-item='short-docbox'>Short</span>\nSummary.</p>"
+        ),
+        "{}",
+        function
+    );
+    assert!(
+        function
+            .contains("<div class='item-info'><div class='stab unstable'>\n<p>Docbox content</p>"),
+        "{}",
+        function
+    );
+    assert!(
+        function.contains(
+            "<div class='item-info'><div class='stab'>Since <span class='since'>1.0.0</span></div></div>"
+        ),
+        "{}",
+        function
+    );
+
+    let index = without_styles(&page("no_js", "index.html"));
+    assert!(
+        index.contains(
+            "<span class='stab unstable' data-doc-item='short-docbox'>Experimental</span>\nSummary.</"
+        ),
+        "{}",
+        index
+    );
+    // Items without any other documentation have empty summaries.
+    assert!(!index.contains("item-info"), "{}", index);
+}
+
+#[test]
+fn styles() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    // Each built-in class's styles are only included with the markup using it, and the crate's
+    // styles are only included with markup using a class other than rustdoc's own.
+    for (name, classes, crate_styles) in &[
+        ("fn.custom_class_function.html", &[][..], true),
+        ("fn.const_function.html", &[], false),
+        ("fn.inline_style_function.html", &["note", "success"], true),
+        ("fn.icon_function.html", &["warning"], true),
+    ] {
+        let page = page("test_target", name);
+        let stylesheets = stylesheets(&page);
+        let built_in = stylesheets
+            .iter()
+            .filter(|link| link.contains("--doc-item-"))
+            .collect::<Vec<_>>();
+        assert_eq!(built_in.len(), classes.len(), "{}: {:?}", name, stylesheets);
+        for class in *classes {
+            let rule = format!(
+                ".stab.{0}{{background-color:var(--doc-item-{0}-background);",
+                class
+            );
+            assert!(
+                built_in.iter().any(|link| link.contains(&rule)),
+                "{}: {:?}",
+                name,
+                built_in
+            );
+        }
+        assert_eq!(
+            stylesheets
+                .iter()
+                .any(|link| link.ends_with(",.custom { background: %23c4ffd7; }'>")),
+            *crate_styles,
+            "{}: {:?}",
+            name,
+            stylesheets
+        );
+    }
+
+    let inline_style = page("test_target", "fn.inline_style_function.html");
+    assert!(
+        inline_style.contains(
+            "<div class='stab note' style='background-color:#c4ffd7;border:1px solid teal'>\n<p><span class='emoji'>🌱</span>\nDocbox content</p>"
+        ),
+        "{}",
+        inline_style
+    );
+    assert!(
+        inline_style.contains(
+            "<span class='stab ' style='color:var(--doc-item-success-border)' data-doc-item='short-docbox'><span class='emoji'>🌱</span> Short</span>"
+        ),
+        "{}",
+        inline_style
+    );
+
+    let icon = page("test_target", "fn.icon_function.html");
+    assert!(
+        icon.contains("<div class='stab warning'>\n<p><span class='emoji'><svg width='1em' height='1em' viewBox='0 0 16 16' fill='currentColor'"),
+        "{}",
+        icon
+    );
+}
+
+#[test]
+fn lib_name() {
+    let output = doc(&fixture("lib_name"));
+    assert!(util::errors(&output).is_empty(), "{}", output);
+
+    // The crate root is found in the standard layout, even though the library isn't named after
+    // the package.
+    let function = page("renamed", "fn.function.html");
+    assert!(
+        stylesheets(&function)
+            .iter()
+            .any(|link| link.ends_with(",.custom { background: %23c4ffd7; }'>")),
+        "{}",
+        function
+    );
+}
+
+#[test]
+fn issues() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    let link = "<a href='https://example.com/issues/42?tracker=a&amp;b'>#42</a>";
+    let function = page("test_target", "fn.issue_function.html");
+    assert!(
+        function.contains(&format!("<p>Docbox content\n(see issue {})</p>", link)),
+        "{}",
+        function
+    );
+    let plain_function = page("test_target", "fn.plain_issue_function.html");
+    assert!(
+        plain_function.contains(&format!("Docbox content (see issue {})</div>", link)),
+        "{}",
+        plain_function
+    );
+    let unstable_function = page("test_target", "fn.unstable_function.html");
+    assert!(
+        unstable_function.contains(
+            "(<code>feature</code>\u{a0}<a href=\"https://example.com/issues/1?tracker=a&amp;b\">#1</a>)"
+        ),
+        "{}",
+        unstable_function
+    );
+
+    let index = page("test_target", "index.html");
+    assert!(
+        index.contains(
+            "Short (see issue <a href='https://example.com/issues/7?tracker=a&amp;b'>#7</a>)</span>"
+        ),
+        "{}",
+        index
+    );
+}
+
+#[test]
+fn presets() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    let link = "<a href='https://example.com/issues/42?tracker=a&amp;b'>#42</a>";
+    let function = page("test_target", "fn.preset_function.html");
+    assert!(
+        function.contains(&format!(
+            "<div class='stab unstable'>\n<p>This is an <em>experimental</em> API.\n(see issue {})</p>",
+            link
+        )),
+        "{}",
+        function
+    );
+    let overridden_function = page("test_target", "fn.overridden_preset_function.html");
+    assert!(
+        overridden_function.contains(&format!(
+            "<div class='stab warning'>\n<p>Overridden content\n(see issue {})</p>",
+            link
+        )),
+        "{}",
+        overridden_function
+    );
+    // Even a flag set by the preset can be overridden.
+    let escaped_function = page("test_target", "fn.escaped_preset_function.html");
+    assert!(
+        escaped_function.contains("<p>&lt;b&gt;Raw&lt;/b&gt; preset</p>"),
+        "{}",
+        escaped_function
+    );
+
+    let index = page("test_target", "index.html");
+    assert!(
+        index.contains(&format!(
+            "<span class='stab unstable' data-doc-item='short-docbox'>Experimental (see issue {})</span>",
+            link
+        )),
+        "{}",
+        index
+    );
+    assert!(
+        index.contains(
+            "<span class='stab security' data-doc-item='short-docbox'><span class='emoji'><svg "
+        ),
+        "{}",
+        index
+    );
+    assert!(
+        index.contains("</svg></span> Available in FIPS mode.</span>"),
+        "{}",
+        index
+    );
+}
+
+#[test]
+fn content_files() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    for name in &[
+        "fn.content_file_function.html",
+        "fn.content_file_preset_function.html",
+    ] {
+        let function = page("test_target", name);
+        assert!(
+            function.contains(
+                "<div class='stab security'>\n<p>Only available in <em>FIPS</em> mode of test_target. See <a href=\"fn.function.html\""
+            ),
+            "{}",
+            function
+        );
+        assert!(
+            function.contains("<p>Requires &lt;fips&gt; support.</p>"),
+            "{}",
+            function
+        );
+    }
+
+    let index = page("test_target", "index.html");
+    assert!(
+        index.contains("<span class='stab security' data-doc-item='short-docbox'>*FIPS*</span>"),
+        "{}",
+        index
+    );
+}
+
+#[test]
+fn macro_content() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    let function = page("test_target", "fn.macro_content_function.html");
+    assert!(function.contains("<p>Version 0.1.0</p>"), "{}", function);
+    // Literal content is kept apart from macro calls, whatever characters it contains.
+    let function = page("test_target", "fn.private_use_function.html");
+    assert!(
+        function.contains("<p>Private use \u{e000}\u{e001}</p>"),
+        "{}",
+        function
+    );
+
+    let index = page("test_target", "index.html");
+    assert!(
+        index.contains(
+            "<span class='stab short-docbox' data-doc-item='short-docbox'>test_target</span>"
+        ),
+        "{}",
+        index
+    );
+    assert!(
+        index.contains(
+            "<span class='stab short-docbox' data-doc-item='short-docbox'>\u{e000}</span>"
+        ),
+        "{}",
+        index
+    );
+}
+
+#[test]
+fn placeholders() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    let function = page("test_target", "fn.placeholder_function.html");
+    assert!(
+        function.contains(
+            "<p>Added to test_target in 0.1.0; see function <code>placeholder_function</code>.</p>"
+        ),
+        "{}",
+        function
+    );
+    assert!(
+        function.contains("<span class='since'>0.1.0</span>"),
+        "{}",
+        function
+    );
+    let structure = page("test_target", "struct.PlaceholderStruct.html");
+    assert!(
+        structure
+            .contains("<div class='stab info'>The struct `PlaceholderStruct` uses {braces}.</div>"),
+        "{}",
+        structure
+    );
+    let const_function = page("test_target", "fn.placeholder_const_function.html");
+    assert!(
+        const_function.contains("<p>See function placeholder_const_function.</p>"),
+        "{}",
+        const_function
+    );
+
+    let index = page("test_target", "index.html");
+    assert!(
+        index.contains(
+            "<span class='stab info' data-doc-item='short-docbox'>test_target 0.1.0</span>"
+        ),
+        "{}",
+        index
+    );
+}
+
+#[test]
+fn text() {
+    let json = json();
+
+    for docs in &[
+        r#"\n> Use `function` *instead*, see [here](https://example.com).""#,
+        r#"\n> docbox content\n\n*Since 1.0.0*""#,
+        r#"\n> **Experimental** – This is an experimental API. (`feature` [#1](<https://example.com/issues/1?tracker=a&b>))""#,
+        r#"\n> **Experimental** – This is an experimental API. (`feature`)\n> \n> Needs \\<more\\> testing.""#,
+        r#"\n*Since 1.0.0, const since 1.1.0*""#,
+        r#"\n> Available on **crate feature `foo`** only.""#,
+        r#"\n> Available on **crate feature `foo` and `custom=\"bar\"`** only.""#,
+    ] {
+        assert!(json.contains(docs), "{}: {}", docs, json);
+    }
+    // The markup, along with the bootstraps and stylesheets it uses, is only included in rustdoc's
+    // HTML output.
+    for html in &["<script", "data:text/css", "item-info", "<div", "data-doc-item"] {
+        assert!(!json.contains(html), "{}: {}", html, json);
+    }
+}
+
+#[test]
+fn inline() {
+    let output = doc(&fixture("inline"));
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    // The item is documented from the dependency's compiled metadata, and is displayed the same as
+    // it is within the dependency's own documentation.
+    let function = page("inline", "fn.markdown_function.html");
+    assert!(
+        function.contains(
+            "<p>Use <code>function</code> <em>instead</em>, see <a href=\"https://example.com\">here</a>.</p>"
+        ),
+        "{}",
+        function
+    );
+    assert!(function.contains("<div class='item-info'"), "{}", function);
+    assert!(!function.contains("<blockquote>"), "{}", function);
+}
+
+#[test]
+fn assets() {
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    // Module lists only include the list bootstrap, once for each item with markup there.
+    let index = page("test_target", "index.html");
+    assert_eq!(bootstraps(&index, true).len(), 29, "{}", index);
+    assert!(bootstraps(&index, false).is_empty(), "{}", index);
+
+    // Each item's page includes a single copy of each bootstrap it uses.
+    for (name, list, page_bootstrap) in &[
+        ("fn.function.html", 1, 1),
+        ("fn.unstable_function.html", 1, 1),
+        ("fn.const_function.html", 0, 1),
+        ("fn.cfg_function.html", 1, 1),
+        ("fn.markdown_function.html", 1, 1),
+        ("fn.raw_doc_function.html", 1, 0),
+    ] {
+        let page = page("test_target", name);
+        assert_eq!(bootstraps(&page, true).len(), *list, "{}: {}", name, page);
+        assert_eq!(
+            bootstraps(&page, false).len(),
+            *page_bootstrap,
+            "{}: {}",
+            name,
+            page
+        );
+        assert!(
+            page.matches("<link rel='stylesheet' data-doc-item-styles")
+                .count()
+                <= 1,
+            "{}: {}",
+            name,
+            page
+        );
+    }
+}
+
+#[test]
+fn size() {
+    let output = doc(&fixture("baseline"));
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+    let output = doc(TEST_TARGET);
+    assert!(util::warnings(&output).is_empty(), "{}", output);
+
+    // The markup is smaller than that generated by doc_item 0.3.0 for the same attributes, both in
+    // module lists and on the item's own page.
+    let baseline_index = page("baseline", "index.html");
+    let index = page("test_target", "index.html");
+    assert!(
+        function_summary(&index).len() < function_summary(&baseline_index).len(),
+        "{}",
+        function_summary(&index)
+    );
+    let baseline_function = page("baseline", "fn.function.html");
+    let function = page("test_target", "fn.function.html");
+    assert!(
+        docblock(&function).len() < docblock(&baseline_function).len(),
+        "{}",
+        docblock(&function)
+    );
+}
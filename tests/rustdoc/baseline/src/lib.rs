@@ -0,0 +1,6 @@
+//! The documentation generated by doc_item 0.3.0 for `test_target::function`, against which the
+//! size of the documentation generated now is compared.
+
+#[doc = "<script>var module_items = document.currentScript.parentElement.parentElement.getElementsByClassName('module-item'); if(module_items.length != 0){{module_items[0].classList.add('unstable');}}document.currentScript.remove();</script><script>document.currentScript.remove();</script><span class='stab short-docbox'>short docbox content</span><script>var box = document.currentScript.previousElementSibling;var classes = document.currentScript.parentElement.parentElement.getElementsByClassName('module-item');if (classes.length == 0) {box.remove();} else {classes[0].append(box);}document.currentScript.remove();</script><div class='item-info'><div class='stab docbox'>docbox content</div></div><script>var box = document.currentScript.previousElementSibling;if(box.parentElement.classList.contains('docblock-short')){box.remove();}else if(box.parentElement.parentElement.classList.contains('top-doc')){box.parentElement.parentElement.before(box);}else{box.parentElement.before(box);}document.currentScript.remove();</script>"]
+#[doc = " <script>document.currentScript.remove();</script><span class='since'>1.0.0</span><script>var since=document.currentScript.previousElementSibling;if(since.parentElement.classList.contains('docblock-short')){since.remove();}else if(since.parentElement.parentElement.classList.contains('top-doc')){var out_of_band = since.parentElement.parentElement.parentElement.getElementsByClassName('out-of-band')[0];out_of_band.prepend(' · ');out_of_band.prepend(since);}else{var rightside = since.parentElement.parentElement.getElementsByClassName('rightside')[0];rightside.prepend(' · ');rightside.prepend(since);}document.currentScript.remove();</script>"]
+pub fn function() {}
@@ -0,0 +1,2 @@
+#[doc(inline)]
+pub use test_target::markdown_function;
@@ -0,0 +1,4 @@
+doc_item::styles!(".custom { background: #c4ffd7; }");
+
+#[doc_item::docbox(content = "Docbox content", class = "custom")]
+pub fn function() {}
@@ -0,0 +1,21 @@
+/// Summary.
+#[doc_item::docbox(content = "Docbox content", class = "unstable")]
+#[doc_item::short_docbox(content = "Short", class = "unstable")]
+#[doc_item::since(content = "1.0.0")]
+#[doc_item::semi_transparent]
+pub fn function() {}
+
+/// Summary.
+#[doc_item::unstable(feature = "foo")]
+#[doc_item::stable(since = "1.0.0")]
+pub fn unstable_function() {}
+
+/// Summary.
+#[doc_item::doc_cfg(feature = "foo")]
+pub fn cfg_function() {}
+
+#[doc_item::docbox(content = "Docbox content", class = "unstable")]
+pub fn undocumented_function() {}
+
+#[doc_item::since(content = "1.0.0")]
+pub fn since_function() {}
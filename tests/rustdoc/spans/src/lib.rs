@@ -0,0 +1,21 @@
+/// A short docbox.
+///
+/// Links to [`Missing`].
+#[doc_item::short_docbox(content = "Short", class = "unstable")]
+pub fn short_docbox() {}
+
+/// A semi-transparent item.
+///
+/// Links to [`Missing`].
+#[doc_item::semi_transparent]
+pub fn semi_transparent() {}
+
+/// An unstable item.
+///
+/// Links to [`Missing`].
+#[doc_item::unstable(feature = "foo")]
+pub fn unstable() {}
+
+/// A docbox.
+#[doc_item::docbox(content = "Links to [`Missing`].", class = "unstable")]
+pub fn docbox() {}
@@ -2,6 +2,8 @@ use doc_item::{
     docbox, semi_transparent, short_docbox, since,
 };
 
+doc_item::styles!(".custom { background: #c4ffd7; }");
+
 #[docbox(content = "docbox content", class = "docbox")]
 #[short_docbox(content = "short docbox content", class = "short-docbox")]
 #[since(content = "1.0.0")]
@@ -37,7 +39,7 @@ pub static STATIC: usize = 0;
 #[since(content = "1.0.0")]
 #[semi_transparent]
 pub union Union {
-    a: usize,
+    pub a: usize,
 }
 
 pub struct Method {}
@@ -71,3 +73,110 @@ pub mod module {}
 #[since(content = "1.0.0")]
 #[semi_transparent]
 pub type Type = usize;
+
+#[doc_item::unstable(feature = "feature", issue = 1)]
+pub fn unstable_function() {}
+
+#[doc_item::stable(since = "1.0.0", const_since = "1.1.0")]
+pub const fn const_function() {}
+
+#[doc_item::doc_cfg(feature = "foo")]
+pub fn cfg_function() {}
+
+#[doc_item::doc_cfg(any(unix, all(target_os = "windows", not(target_pointer_width = "16"))), apply)]
+pub fn applied_cfg_function() {}
+
+#[docbox(
+    content = "Use `function` *instead*, see [here](https://example.com).",
+    class = "docbox"
+)]
+#[short_docbox(content = "*Markdown*", class = "short-docbox")]
+pub fn markdown_function() {}
+
+#[docbox(content = "Use [`function`] instead.", class = "docbox")]
+pub fn intra_doc_link_function() {}
+
+#[docbox(content = "Prefer <b>bold</b> & 'quotes'", class = "docbox")]
+#[short_docbox(content = "<i>Escaped</i>", class = "short-docbox")]
+pub fn escaped_function() {}
+
+#[docbox(content = "<b>Raw</b>", class = "docbox", raw_html)]
+#[short_docbox(content = "<i>Raw</i>", class = "short-docbox", raw_html)]
+pub fn raw_html_function() {}
+
+#[short_docbox(content = "Raw", class = "short-docbox")]
+#[doc(alias = "raw_doc")]
+#[doc = r#"Raw "doc" string."#]
+pub fn raw_doc_function() {}
+
+#[short_docbox(content = "Included", class = "short-docbox")]
+#[doc = include_str!("../docs/included.md")]
+pub fn include_str_function() {}
+
+#[semi_transparent]
+#[doc = concat!("Concatenated ", "docs.")]
+pub fn concat_function() {}
+
+#[docbox(content = concat!("Version ", env!("CARGO_PKG_VERSION")), class = "docbox")]
+#[short_docbox(content = env!("CARGO_PKG_NAME"), class = "short-docbox")]
+pub fn macro_content_function() {}
+
+#[docbox(content = "Private use \u{e000}\u{e001}", class = "docbox")]
+#[short_docbox(content = "\u{e000}", class = "short-docbox")]
+pub fn private_use_function() {}
+
+#[doc_item::unstable(feature = "feature", reason = "Needs <more> testing.")]
+pub fn reason_function() {}
+
+#[doc_item::doc_cfg(all(feature = "foo", custom = "bar"))]
+pub fn custom_cfg_function() {}
+
+#[docbox(content = "Docbox content", class = "unstable", issue = 42)]
+pub fn issue_function() {}
+
+#[docbox(content = "Docbox content", class = "unstable", markdown = false, issue = 42)]
+#[short_docbox(content = "Short", class = "unstable", issue = 7)]
+pub fn plain_issue_function() {}
+
+#[docbox(preset = "experimental")]
+#[short_docbox(preset = "experimental")]
+pub fn preset_function() {}
+
+#[docbox(preset = "experimental", content = "Overridden content", class = "warning")]
+pub fn overridden_preset_function() {}
+
+#[docbox(preset = "fips")]
+#[short_docbox(preset = "fips")]
+pub fn fallback_preset_function() {}
+
+#[docbox(preset = "raw", raw_html = false)]
+pub fn escaped_preset_function() {}
+
+#[docbox(content_file = "docs/notices/fips.md", class = "security")]
+#[short_docbox(content_file = "docs/notices/fips_short.md", class = "security", markdown = false)]
+pub fn content_file_function() {}
+
+#[docbox(preset = "fips_notice")]
+pub fn content_file_preset_function() {}
+
+#[docbox(content = "Added to {crate} in {version}; see {kind} `{item}`.", class = "info")]
+#[short_docbox(content = "{crate} {version}", class = "info")]
+#[since(content = "{version}")]
+pub fn placeholder_function() {}
+
+#[docbox(content = "The {kind} `{item}` uses {{braces}}.", class = "info", markdown = false)]
+pub struct PlaceholderStruct;
+
+#[docbox(content = "See {kind} {item}.", class = "info")]
+pub const unsafe fn r#placeholder_const_function() {}
+
+#[docbox(content = "Docbox content", class = "custom")]
+#[short_docbox(content = "Short", class = "custom")]
+pub fn custom_class_function() {}
+
+#[docbox(content = "Docbox content", class = "note", background = "#c4ffd7", border = "teal", icon = "🌱")]
+#[short_docbox(content = "Short", color = "var(--doc-item-success-border)", icon = "🌱")]
+pub fn inline_style_function() {}
+
+#[docbox(content = "Docbox content", class = "warning", icon = "warning")]
+pub fn icon_function() {}
@@ -15,3 +15,27 @@ fn short_docbox() {
 fn since() {
     trybuild::TestCases::new().compile_fail("tests/ui/since/*.rs");
 }
+
+#[rustversion::attr(not(nightly), ignore)]
+#[test]
+fn unstable() {
+    trybuild::TestCases::new().compile_fail("tests/ui/unstable/*.rs");
+}
+
+#[rustversion::attr(not(nightly), ignore)]
+#[test]
+fn stable() {
+    trybuild::TestCases::new().compile_fail("tests/ui/stable/*.rs");
+}
+
+#[rustversion::attr(not(nightly), ignore)]
+#[test]
+fn doc_cfg() {
+    trybuild::TestCases::new().compile_fail("tests/ui/doc_cfg/*.rs");
+}
+
+#[rustversion::attr(not(nightly), ignore)]
+#[test]
+fn styles() {
+    trybuild::TestCases::new().compile_fail("tests/ui/styles/*.rs");
+}
@@ -0,0 +1,4 @@
+#[doc_item::doc_cfg(not(unix, windows))]
+fn multiple_not() {}
+
+fn main() {}
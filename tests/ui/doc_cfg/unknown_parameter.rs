@@ -0,0 +1,4 @@
+#[doc_item::doc_cfg(unix, windows)]
+fn unknown_parameter() {}
+
+fn main() {}
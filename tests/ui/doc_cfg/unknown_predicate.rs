@@ -0,0 +1,4 @@
+#[doc_item::doc_cfg(either(unix, windows))]
+fn unknown_predicate() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", content_file="docs/notice.md")]
+fn content_and_content_file() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", class="stab' onclick='alert()")]
+fn invalid_class() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", background="#c4ffd7", border="rgb(0 0 0)';color:red", color="blurple")]
+fn invalid_color() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", issue=42)]
+fn issue_without_tracker() {}
+
+fn main() {}
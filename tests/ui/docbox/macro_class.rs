@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", class=concat!("x' style='", "color:red"))]
+fn macro_class() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="<script>alert()</script>", class="unstable", raw_html)]
+fn script_tag() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="<span>Unclosed", class="unstable", raw_html)]
+fn unclosed_tag() {}
+
+fn main() {}
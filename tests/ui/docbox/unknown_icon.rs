@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Content", icon="warnign")]
+fn unknown_icon() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(content="Added in {release}")]
+fn unknown_placeholder() {}
+
+fn main() {}
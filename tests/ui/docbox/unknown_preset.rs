@@ -0,0 +1,6 @@
+use doc_item::docbox;
+
+#[docbox(preset="experimental")]
+fn unknown_preset() {}
+
+fn main() {}
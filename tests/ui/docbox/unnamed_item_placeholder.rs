@@ -0,0 +1,8 @@
+use doc_item::docbox;
+
+struct Foo;
+
+#[docbox(content="See {item}")]
+impl Foo {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="Content", class="1st")]
+fn invalid_class() {}
+
+fn main() {}
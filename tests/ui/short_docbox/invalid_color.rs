@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="Content", background="#c4ffd")]
+fn invalid_color() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="<b><i>Mismatched</b></i>", class="unstable", raw_html, markdown=false)]
+fn mismatched_tag() {}
+
+fn main() {}
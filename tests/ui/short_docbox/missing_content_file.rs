@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content_file="docs/missing.md", class="unstable")]
+fn missing_content_file() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="Content", preset="experimental")]
+fn unknown_preset() {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="Set }")]
+fn unmatched_brace() {}
+
+fn main() {}
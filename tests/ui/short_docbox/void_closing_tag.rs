@@ -0,0 +1,6 @@
+use doc_item::short_docbox;
+
+#[short_docbox(content="Line<br></br>", class="unstable", raw_html)]
+fn void_closing_tag() {}
+
+fn main() {}
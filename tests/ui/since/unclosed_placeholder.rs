@@ -0,0 +1,6 @@
+use doc_item::since;
+
+#[since(content="{version")]
+fn unclosed_placeholder() {}
+
+fn main() {}
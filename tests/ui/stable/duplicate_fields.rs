@@ -0,0 +1,4 @@
+#[doc_item::stable(since="1.0.0", since="1.1.0")]
+fn duplicate_fields() {}
+
+fn main() {}
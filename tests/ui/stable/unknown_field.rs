@@ -0,0 +1,4 @@
+#[doc_item::stable(since="1.0.0", invalid="field")]
+fn unknown_field() {}
+
+fn main() {}
@@ -0,0 +1,5 @@
+mod module {
+    doc_item::styles!(".custom { background: #c4ffd7; }");
+}
+
+fn main() {}
@@ -0,0 +1,3 @@
+doc_item::styles!(concat!(".custom ", "{ background: #c4ffd7; }"));
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[doc_item::unstable(feature="feature 1", feature="feature 2")]
+fn duplicate_fields() {}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[doc_item::unstable(feature="foo", issue=42)]
+fn issue_without_tracker() {}
+
+fn main() {}
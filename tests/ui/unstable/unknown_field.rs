@@ -0,0 +1,4 @@
+#[doc_item::unstable(feature="foo", invalid="field")]
+fn unknown_field() {}
+
+fn main() {}
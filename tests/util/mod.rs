@@ -0,0 +1,51 @@
+//! Helpers shared by the tests building crates that use the attributes.
+//!
+//! Each test file uses its own target directory, so that the tests of different files can run
+//! concurrently without affecting each other's output.
+
+#![allow(dead_code)]
+
+use std::{fs, process::Command};
+
+/// The manifest of the crate used by most tests.
+pub const TEST_TARGET: &str = "tests/test_target/Cargo.toml";
+
+/// Runs the Cargo subcommand on the crate with the given manifest, returning its diagnostics.
+pub fn cargo(subcommand: &str, manifest_path: &str, target_dir: &str) -> String {
+    let mut command = Command::new("cargo");
+    command.arg(subcommand);
+    if subcommand == "doc" {
+        command.arg("--no-deps");
+    }
+    let output = command
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .arg("--message-format")
+        .arg("short")
+        .arg("--quiet")
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .unwrap_or_else(|_| panic!("Could not run `cargo {}`", subcommand));
+    String::from_utf8(output.stderr)
+        .unwrap_or_else(|_| panic!("`cargo {}` output was not valid UTF-8", subcommand))
+}
+
+/// Reads a page generated for the crate.
+pub fn page(target_dir: &str, name: &str, page: &str) -> String {
+    fs::read_to_string(format!("{}/doc/{}/{}", target_dir, name, page))
+        .expect("Could not read generated page")
+}
+
+pub fn warnings(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .filter(|line| line.contains("warning: ") && !line.contains("generated"))
+        .collect()
+}
+
+pub fn errors(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .filter(|line| line.contains("error: ") && !line.starts_with("error: could not compile"))
+        .collect()
+}